@@ -0,0 +1,453 @@
+use crate::{Scalar, Sense, SimplexError, Solution, Table};
+use ndarray::{Array1, Array2};
+
+// solutions of a task and of its dual, mapped onto each other
+#[derive(Debug, Clone, PartialEq)]
+pub struct Duality<T = f64> {
+    pub primal: Solution<T>,
+    pub dual: Solution<T>,
+    // dual variables, one for every primal constraint
+    pub shadow_prices: Vec<T>,
+    // dual slacks, one for every primal variable
+    pub reduced_costs: Vec<T>,
+    // difference of the primal and the dual objectives
+    pub gap: T,
+}
+
+// condition of optimality that doesn't hold, with the amount of the violation
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation<T = f64> {
+    // primal constraint exceeded by the value
+    PrimalConstraint(String, T),
+    // primal variable below zero by the value
+    PrimalSign(String, T),
+    // dual constraint of the primal variable exceeded by the value
+    DualConstraint(String, T),
+    // dual variable of the primal constraint on the wrong side of zero by the value
+    DualSign(String, T),
+    // product of the slack of the primal constraint and its dual variable
+    ConstraintSlackness(String, T),
+    // product of the primal variable and the slack of its dual constraint
+    VariableSlackness(String, T),
+}
+
+// result of checking a primal and a dual solution against the task data
+#[derive(Debug, Clone, PartialEq)]
+pub struct Certificate<T = f64> {
+    pub primal_objective: T,
+    pub dual_objective: T,
+    pub violations: Vec<Violation<T>>,
+}
+
+impl<T: Scalar> Certificate<T> {
+    // recomputes everything from the data of Table::new,
+    // so nothing depends on the table the solutions came from
+    pub fn check(
+        constr_coeff: &Array2<T>,
+        constr_val: &Array1<T>,
+        func_coeff: &Array1<T>,
+        minimisation_task: bool,
+        primal: &Solution<T>,
+        dual: &Solution<T>,
+        tolerance: T,
+    ) -> Result<Certificate<T>, SimplexError<T>> {
+        let (m, n) = (constr_coeff.nrows(), constr_coeff.ncols());
+        if constr_val.len() != m
+            || func_coeff.len() != n
+            || primal.variables.len() != n
+            || dual.variables.len() != m
+        {
+            return Err(SimplexError::InvalidDataError);
+        }
+        let (x, y) = (&primal.variables, &dual.variables);
+        // dual variables are not negative for a maximisation task and not positive otherwise
+        let sign = if minimisation_task {
+            -T::one()
+        } else {
+            T::one()
+        };
+        let mut violations = Vec::new();
+        let slacks: Vec<T> = (0..m)
+            .map(|i| {
+                constr_val[i].clone()
+                    - (0..n).fold(T::zero(), |acc, j| {
+                        acc + constr_coeff[[i, j]].clone() * x[j].clone()
+                    })
+            })
+            .collect();
+        let dual_slacks: Vec<T> = (0..n)
+            .map(|j| {
+                let row = (0..m).fold(T::zero(), |acc, i| {
+                    acc + constr_coeff[[i, j]].clone() * y[i].clone()
+                });
+                sign.clone() * (row - func_coeff[j].clone())
+            })
+            .collect();
+        for (i, name) in primal.constraint_names.iter().enumerate() {
+            if slacks[i] < -tolerance.clone() {
+                violations.push(Violation::PrimalConstraint(
+                    name.clone(),
+                    -slacks[i].clone(),
+                ));
+            }
+            if sign.clone() * y[i].clone() < -tolerance.clone() {
+                violations.push(Violation::DualSign(
+                    name.clone(),
+                    (sign.clone() * y[i].clone()).abs(),
+                ));
+            }
+            let product = slacks[i].clone() * y[i].clone();
+            if product.abs() > tolerance {
+                violations.push(Violation::ConstraintSlackness(name.clone(), product));
+            }
+        }
+        for (j, name) in primal.variable_names.iter().enumerate() {
+            if x[j] < -tolerance.clone() {
+                violations.push(Violation::PrimalSign(name.clone(), -x[j].clone()));
+            }
+            if dual_slacks[j] < -tolerance.clone() {
+                violations.push(Violation::DualConstraint(
+                    name.clone(),
+                    -dual_slacks[j].clone(),
+                ));
+            }
+            let product = x[j].clone() * dual_slacks[j].clone();
+            if product.abs() > tolerance {
+                violations.push(Violation::VariableSlackness(name.clone(), product));
+            }
+        }
+        Ok(Certificate {
+            primal_objective: (0..n).fold(T::zero(), |acc, j| {
+                acc + func_coeff[j].clone() * x[j].clone()
+            }),
+            dual_objective: (0..m).fold(T::zero(), |acc, i| {
+                acc + constr_val[i].clone() * y[i].clone()
+            }),
+            violations,
+        })
+    }
+
+    pub fn is_optimal(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+// proof that no x within the bounds satisfies the constraints: y'Ax <= y'b holds for every
+// feasible x, as y is not negative on <= rows, not positive on >= rows and free on equalities,
+// but y'Ax is greater than y'b everywhere within the bounds
+#[derive(Debug, Clone, PartialEq)]
+pub struct Farkas<T = f64> {
+    pub multipliers: Vec<T>,
+    pub constraint_names: Vec<String>,
+}
+
+impl<T: Scalar> Farkas<T> {
+    // checks the multipliers against the task data, nothing else is taken from the proof
+    pub fn check(
+        &self,
+        constr_coeff: &Array2<T>,
+        constr_val: &Array1<T>,
+        senses: &[Sense],
+        lower_bounds: &[Option<T>],
+        upper_bounds: &[Option<T>],
+        tolerance: T,
+    ) -> bool {
+        let (m, n) = (constr_coeff.nrows(), constr_coeff.ncols());
+        if self.multipliers.len() != m
+            || constr_val.len() != m
+            || senses.len() != m
+            || lower_bounds.len() != n
+            || upper_bounds.len() != n
+        {
+            return false;
+        }
+        let signs_hold = self
+            .multipliers
+            .iter()
+            .zip(senses.iter())
+            .all(|(y, sense)| match sense {
+                Sense::LessEqual => *y >= -tolerance.clone(),
+                Sense::GreaterEqual => *y <= tolerance,
+                Sense::Equal => true,
+            });
+        if !signs_hold {
+            return false;
+        }
+        // smallest value of y'Ax within the bounds
+        let mut lowest = T::zero();
+        for j in 0..n {
+            let coeff = (0..m).fold(T::zero(), |acc, i| {
+                acc + self.multipliers[i].clone() * constr_coeff[[i, j]].clone()
+            });
+            let bound = if coeff > tolerance {
+                lower_bounds[j].clone()
+            } else if coeff < -tolerance.clone() {
+                upper_bounds[j].clone()
+            } else {
+                continue;
+            };
+            match bound {
+                Some(bound) => lowest += coeff * bound,
+                None => return false,
+            }
+        }
+        let rhs = (0..m).fold(T::zero(), |acc, i| {
+            acc + self.multipliers[i].clone() * constr_val[i].clone()
+        });
+        lowest > rhs + tolerance
+    }
+}
+
+// proof that the objective is unlimited: vertex + step * direction stays feasible for every
+// step >= 0 and the objective changes by objective_rate for every unit of the step
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ray<T = f64> {
+    pub vertex: Vec<T>,
+    pub direction: Vec<T>,
+    // change of the constraint slacks along the direction
+    pub slack_direction: Vec<T>,
+    pub objective_rate: T,
+    pub variable_names: Vec<String>,
+    pub constraint_names: Vec<String>,
+}
+
+impl<T: Scalar> Ray<T> {
+    // checks the direction against the task data, the vertex is not checked
+    #[allow(clippy::too_many_arguments)]
+    pub fn check(
+        &self,
+        constr_coeff: &Array2<T>,
+        func_coeff: &Array1<T>,
+        minimisation_task: bool,
+        senses: &[Sense],
+        lower_bounds: &[Option<T>],
+        upper_bounds: &[Option<T>],
+        tolerance: T,
+    ) -> bool {
+        let (m, n) = (constr_coeff.nrows(), constr_coeff.ncols());
+        if self.direction.len() != n
+            || func_coeff.len() != n
+            || senses.len() != m
+            || lower_bounds.len() != n
+            || upper_bounds.len() != n
+        {
+            return false;
+        }
+        let rows_hold = senses.iter().enumerate().all(|(i, sense)| {
+            let change = (0..n).fold(T::zero(), |acc, j| {
+                acc + constr_coeff[[i, j]].clone() * self.direction[j].clone()
+            });
+            match sense {
+                Sense::LessEqual => change <= tolerance,
+                Sense::GreaterEqual => change >= -tolerance.clone(),
+                Sense::Equal => change.abs() <= tolerance,
+            }
+        });
+        let bounds_hold = (0..n).all(|j| {
+            (lower_bounds[j].is_none() || self.direction[j] >= -tolerance.clone())
+                && (upper_bounds[j].is_none() || self.direction[j] <= tolerance)
+        });
+        let rate = (0..n).fold(T::zero(), |acc, j| {
+            acc + func_coeff[j].clone() * self.direction[j].clone()
+        });
+        let improving = if minimisation_task {
+            rate < -tolerance
+        } else {
+            rate > tolerance
+        };
+        rows_hold && bounds_hold && improving
+    }
+}
+
+impl<T: Scalar> Duality<T> {
+    // checks the solutions against the data they were solved from
+    pub fn verify(
+        &self,
+        constr_coeff: &Array2<T>,
+        constr_val: &Array1<T>,
+        func_coeff: &Array1<T>,
+        minimisation_task: bool,
+    ) -> Result<Certificate<T>, SimplexError<T>> {
+        Certificate::check(
+            constr_coeff,
+            constr_val,
+            func_coeff,
+            minimisation_task,
+            &self.primal,
+            &self.dual,
+            T::default_tolerance(),
+        )
+    }
+}
+
+impl<T: Scalar> Table<T> {
+    // the row shows the constraints can't hold, its multipliers of the original rows
+    // are the coeffs of their slacks
+    pub(crate) fn farkas(&self, row: usize) -> Farkas<T> {
+        // an equality row can be violated from above
+        let direction = if self.free_coeff(row).is_positive() {
+            -T::one()
+        } else {
+            T::one()
+        };
+        self.farkas_from(self.slack_coeffs(row), direction)
+    }
+
+    // the penalty row is the sum of the rows of basic artificial variables, so once phase one
+    // can't lower the positive artificial sum any more, their coeffs prove the same
+    pub(crate) fn phase_one_farkas(&self) -> Farkas<T> {
+        let last = self.table.nrows() - 1;
+        let mut coeffs = vec![T::zero(); self.constraints.len()];
+        for i in 0..last {
+            if self.artificial.contains(&self.supp_var[i]) {
+                for (sum, coeff) in coeffs.iter_mut().zip(self.slack_coeffs(i)) {
+                    *sum += coeff;
+                }
+            }
+        }
+        self.farkas_from(coeffs, -T::one())
+    }
+
+    // coeff of the slack of every constraint in the row, the row is the same combination
+    // of the original rows
+    fn slack_coeffs(&self, row: usize) -> Vec<T> {
+        let last = self.shape().1 - 1;
+        self.constraints
+            .iter()
+            .map(|name| {
+                if self.supp_var[row] == *name {
+                    T::one()
+                } else if let Some(j) = self.base_var[..last].iter().position(|i| i == name) {
+                    self.entry(row, j)
+                } else if let Some((_, column)) = self.fixed_columns.iter().find(|(i, _)| i == name)
+                {
+                    column[row].clone()
+                } else {
+                    T::zero()
+                }
+            })
+            .collect()
+    }
+
+    // multipliers of the original rows from the slack coeffs of a combination of the table rows,
+    // the direction is minus one if the combination has a positive free coeff
+    pub(crate) fn farkas_from(&self, coeffs: Vec<T>, direction: T) -> Farkas<T> {
+        let multipliers = coeffs
+            .into_iter()
+            .zip(self.senses.iter())
+            .enumerate()
+            .map(|(i, (coeff, sense))| {
+                // multipliers of scaled rows apply to the original ones times the factor
+                let coeff = self.row_factor(i) * coeff;
+                // >= rows are negated in the table
+                if *sense == Sense::GreaterEqual {
+                    -direction.clone() * coeff
+                } else {
+                    direction.clone() * coeff
+                }
+            })
+            .collect();
+        Farkas {
+            multipliers,
+            constraint_names: self.constraints.clone(),
+        }
+    }
+
+    // the column improves the function but no row limits it, so moving its variable up
+    // moves every basic variable by minus its coeff in the column
+    pub(crate) fn ray(&self, column: usize) -> Ray<T> {
+        let last = self.shape().0 - 1;
+        let direction = (0..self.variables.len())
+            .map(|i| -self.variable_expression(i)[column].clone() * self.column_factor(i))
+            .collect();
+        let slack_direction = self
+            .constraints
+            .iter()
+            .enumerate()
+            .map(|(i, name)| -self.expression(name)[column].clone() / self.row_factor(i))
+            .collect();
+        // the function row is Q = S - f x, Q is the objective of a minimisation task
+        let rate = self.entry(last, column);
+        Ray {
+            vertex: self.solution().variables,
+            direction,
+            slack_direction,
+            objective_rate: if self.minimisation_task { -rate } else { rate },
+            variable_names: self.variables.clone(),
+            constraint_names: self.constraints.clone(),
+        }
+    }
+
+    // dual of the task Table::new would build from the same arguments:
+    // max cx, Ax <= b, x >= 0 turns into min by, A'y >= c, y >= 0
+    // and min cx, Ax <= b, x >= 0 into max by, A'y <= c, y <= 0
+    pub fn new_dual(
+        constr_coeff: Array2<T>,
+        constr_val: Array1<T>,
+        func_coeff: Array1<T>,
+        minimisation_task: bool,
+    ) -> Result<Table<T>, SimplexError<T>> {
+        let (m, n) = (constr_coeff.nrows(), constr_coeff.ncols());
+        if constr_val.len() != m || func_coeff.len() != n {
+            return Err(SimplexError::InvalidDataError);
+        }
+        let transposed = Array2::from_shape_fn((n, m), |(i, j)| constr_coeff[[j, i]].clone());
+        let mut table = Table::new(transposed, func_coeff, constr_val, !minimisation_task)?;
+        // every dual variable is named after its primal constraint and the other way round
+        let variables: Vec<String> = (n + 1..n + m + 1).map(|i| i.to_string()).collect();
+        let constraints: Vec<String> = (1..n + 1).map(|i| i.to_string()).collect();
+        table.set_names(
+            &variables.iter().map(|i| i.as_str()).collect::<Vec<_>>(),
+            &constraints.iter().map(|i| i.as_str()).collect::<Vec<_>>(),
+        )?;
+        if minimisation_task {
+            table.lower_bounds = vec![None; m];
+            table.upper_bounds = vec![Some(T::zero()); m];
+        } else {
+            table.set_senses(&vec![Sense::GreaterEqual; n])?;
+        }
+        Ok(table)
+    }
+
+    // solves the task and its dual and checks that the objectives match
+    pub fn solve_with_dual(
+        constr_coeff: Array2<T>,
+        constr_val: Array1<T>,
+        func_coeff: Array1<T>,
+        minimisation_task: bool,
+    ) -> Result<Duality<T>, SimplexError<T>> {
+        let mut dual = Table::new_dual(
+            constr_coeff.clone(),
+            constr_val.clone(),
+            func_coeff.clone(),
+            minimisation_task,
+        )?;
+        let mut primal = Table::new(constr_coeff, constr_val, func_coeff, minimisation_task)?;
+        let primal = primal.optimise()?;
+        let dual = dual.optimise()?;
+        let gap = primal.objective.clone() - dual.objective.clone();
+        let tolerance = T::default_tolerance() * (T::one() + primal.objective.abs());
+        if gap.abs() > tolerance {
+            return Err(SimplexError::DualityGapError(gap));
+        }
+        // c - A'y is the surplus of a dual >= row and the slack of a dual <= row
+        let reduced_costs = dual
+            .slacks
+            .iter()
+            .map(|i| {
+                if minimisation_task {
+                    i.clone()
+                } else {
+                    -i.clone()
+                }
+            })
+            .collect();
+        Ok(Duality {
+            shadow_prices: dual.variables.clone(),
+            reduced_costs,
+            primal,
+            dual,
+            gap,
+        })
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{Farkas, Scalar, SimplexError, Table};
+use log::debug;
+
+impl<T: Scalar> Table<T> {
+    // irreducible infeasible subset: names of constraints that can't hold together,
+    // while dropping any one of them makes the rest feasible within the bounds
+    pub fn iis(&self) -> Result<Vec<String>, SimplexError<T>> {
+        self.check_unoptimised()?;
+        let mut keep = vec![true; self.constraints.len()];
+        let farkas = match self.restricted_feasibility(&keep)? {
+            Some(farkas) => farkas,
+            // a feasible task has nothing to explain
+            None => return Err(SimplexError::InvalidDataError),
+        };
+        // bounds that conflict by themselves leave no rows to blame
+        if self
+            .restricted_feasibility(&vec![false; keep.len()])?
+            .is_some()
+        {
+            debug!("IIS: the bounds have no solutions without any rows");
+            return Err(SimplexError::NoSolutionsError(None));
+        }
+        // rows left out of the proof are not needed for the conflict
+        if let Some(multipliers) = farkas {
+            let support: Vec<bool> = multipliers
+                .iter()
+                .map(|y| y.abs() > self.tolerance.feasibility)
+                .collect();
+            if support != keep && self.restricted_feasibility(&support)?.is_some() {
+                debug!(
+                    "IIS: proof of infeasibility keeps {} of {} rows",
+                    support.iter().filter(|&&i| i).count(),
+                    keep.len()
+                );
+                keep = support;
+            }
+        }
+        // deletion filter: a row stays only if the others are feasible without it
+        for i in 0..keep.len() {
+            if !keep[i] {
+                continue;
+            }
+            keep[i] = false;
+            if self.restricted_feasibility(&keep)?.is_some() {
+                debug!("IIS: row {} is not needed", self.constraints[i]);
+            } else {
+                keep[i] = true;
+            }
+        }
+        Ok(self
+            .constraints
+            .iter()
+            .zip(keep.iter())
+            .filter(|(_, &keep)| keep)
+            .map(|(name, _)| name.clone())
+            .collect())
+    }
+
+    // looks for a point satisfying the kept rows, returns None if there is one,
+    // otherwise Some with the Farkas multipliers of all the rows if they were found
+    fn restricted_feasibility(
+        &self,
+        keep: &[bool],
+    ) -> Result<Option<Option<Vec<T>>>, SimplexError<T>> {
+        let mut table = self.clone();
+        table.densify();
+        for i in (0..keep.len()).rev() {
+            if !keep[i] {
+                table.remove_row(i);
+                table.constraints.remove(i);
+                table.senses.remove(i);
+            }
+        }
+        // any objective will do, a zero one can't be unlimited
+        let last = table.table.nrows() - 1;
+        table.table.row_mut(last).fill(T::zero());
+        // multipliers of the removed rows are zero
+        let spread = |farkas: Farkas<T>| {
+            let mut multipliers = farkas.multipliers.into_iter();
+            keep.iter()
+                .map(|&i| {
+                    if i {
+                        multipliers.next().unwrap_or(T::zero())
+                    } else {
+                        T::zero()
+                    }
+                })
+                .collect()
+        };
+        match table.optimise() {
+            Ok(_) | Err(SimplexError::UnlimitedError(_)) => Ok(None),
+            Err(SimplexError::NoSolutionsError(farkas)) => Ok(Some(farkas.map(spread))),
+            Err(SimplexError::ArtificialSumError(_, farkas)) => Ok(Some(Some(spread(farkas)))),
+            Err(error) => Err(error),
+        }
+    }
+}
@@ -0,0 +1,336 @@
+use crate::{
+    BranchingRule, Integrality, NodeSelection, Scalar, Scaling, SimplexError, Solution, Table,
+};
+use log::debug;
+use ndarray::Array1;
+
+// best integer solution found by branch and bound
+#[derive(Debug, Clone, PartialEq)]
+pub struct BranchAndBound<T = f64> {
+    pub solution: Solution<T>,
+    // objective no integer solution can beat, the same as the found one unless the search
+    // stopped at the node limit
+    pub bound: T,
+    // distance between the bound and the objective of the solution
+    pub gap: T,
+    // number of solved subtasks
+    pub nodes: usize,
+}
+
+// subtask with tightened bounds, its objective can't be better than the one of its parent
+struct Node<T> {
+    lower_bounds: Vec<Option<T>>,
+    upper_bounds: Vec<Option<T>>,
+    bound: Option<T>,
+    depth: usize,
+}
+
+impl<T: Scalar> Table<T> {
+    // solves the task with integer values of the integer and binary variables,
+    // leaves the table of the best node in it
+    pub fn branch_and_bound(&mut self) -> Result<BranchAndBound<T>, SimplexError<T>> {
+        self.check_unoptimised()?;
+        if self.integrality.len() != self.variables.len()
+            || self.lower_bounds.len() != self.variables.len()
+            || self.upper_bounds.len() != self.variables.len()
+        {
+            return Err(SimplexError::InvalidDataError);
+        }
+        let mut open = vec![self.root_node()?];
+        let mut best: Option<(Solution<T>, Table<T>)> = None;
+        let mut nodes = 0;
+        while let Some(node) = self.next_node(&mut open) {
+            if self.max_nodes.is_some_and(|max| nodes >= max) {
+                debug!("Branch and bound stopped after {} nodes", nodes);
+                open.push(node);
+                break;
+            }
+            let incumbent = best
+                .as_ref()
+                .map(|(solution, _)| solution.objective.clone());
+            if let (Some(bound), Some(incumbent)) = (node.bound.clone(), incumbent.clone()) {
+                if !self.is_better(bound, incumbent) {
+                    continue;
+                }
+            }
+            nodes += 1;
+            let mut table = self.clone();
+            table.lower_bounds = node.lower_bounds.clone();
+            table.upper_bounds = node.upper_bounds.clone();
+            let solution = match table.optimise() {
+                Ok(solution) => solution,
+                Err(SimplexError::NoSolutionsError(_))
+                | Err(SimplexError::ArtificialSumError(..)) => {
+                    debug!("Node {} at depth {} has no solutions", nodes, node.depth);
+                    continue;
+                }
+                Err(error) => return Err(error),
+            };
+            debug!(
+                "Node {} at depth {}: objective {}, best {:?}",
+                nodes, node.depth, solution.objective, incumbent
+            );
+            if incumbent
+                .is_some_and(|incumbent| !self.is_better(solution.objective.clone(), incumbent))
+            {
+                continue;
+            }
+            let j = match self.branching_variable(&solution) {
+                Some(j) => j,
+                None => {
+                    debug!("Node {} has an integer solution", nodes);
+                    best = Some((solution, table));
+                    continue;
+                }
+            };
+            let value = solution.variables[j].clone();
+            let down = floor(value.clone());
+            let mut lower = Node {
+                lower_bounds: node.lower_bounds.clone(),
+                upper_bounds: node.upper_bounds.clone(),
+                bound: Some(solution.objective.clone()),
+                depth: node.depth + 1,
+            };
+            lower.upper_bounds[j] = Some(down.clone());
+            let mut upper = Node {
+                lower_bounds: node.lower_bounds,
+                upper_bounds: node.upper_bounds,
+                bound: Some(solution.objective.clone()),
+                depth: node.depth + 1,
+            };
+            upper.lower_bounds[j] = Some(down.clone() + T::one());
+            debug!("Branching on {} = {}", self.variables[j], value);
+            // the side the value is rounded to is solved first
+            if (value - down) * (T::one() + T::one()) < T::one() {
+                open.push(upper);
+                open.push(lower);
+            } else {
+                open.push(lower);
+                open.push(upper);
+            }
+        }
+        let (solution, table) = match best {
+            Some(best) => best,
+            None if open.is_empty() => return Err(SimplexError::NoSolutionsError(None)),
+            None => return Err(SimplexError::UnableToCalculateError),
+        };
+        // nodes left at the limit may still hold better solutions
+        let bound = open.iter().filter_map(|node| node.bound.clone()).fold(
+            solution.objective.clone(),
+            |acc, i| {
+                if self.is_better(i.clone(), acc.clone()) {
+                    i
+                } else {
+                    acc
+                }
+            },
+        );
+        let gap = (bound.clone() - solution.objective.clone()).abs();
+        debug!(
+            "Branch and bound finished after {} nodes with gap {}",
+            nodes, gap
+        );
+        *self = table;
+        Ok(BranchAndBound {
+            solution,
+            bound,
+            gap,
+            nodes,
+        })
+    }
+
+    // cutting planes instead of branching: while a row has a fractional value, the fractional
+    // parts of its coeffs give a cut every integer point satisfies, but the current one doesn't.
+    // every variable and coeff has to be integer
+    pub fn gomory(&mut self) -> Result<Solution<T>, SimplexError<T>> {
+        self.check_unoptimised()?;
+        if self.scaling != Scaling::None
+            || self.integrality.len() != self.variables.len()
+            || self.integrality.contains(&Integrality::Continuous)
+        {
+            return Err(SimplexError::InvalidDataError);
+        }
+        // slacks are integer only if the constraints are
+        let (nrows, ncols) = self.shape();
+        let integer_data = (0..ncols).all(|j| {
+            self.table_column(j)
+                .into_iter()
+                .all(|(i, value)| i == nrows - 1 || self.fraction(value).is_zero())
+        });
+        if !integer_data {
+            return Err(SimplexError::InvalidDataError);
+        }
+        let root = self.root_node()?;
+        self.lower_bounds = root.lower_bounds;
+        self.upper_bounds = root.upper_bounds;
+        self.optimise()?;
+        // cuts are added to the dense table
+        self.densify();
+        debug!("Continuous optimum:\n{}", self);
+        let mut cuts = 0;
+        while let Some(row) = self.cut_row() {
+            if self.max_cuts.is_some_and(|max| cuts >= max) {
+                debug!("Cut limit {} reached", cuts);
+                return Err(SimplexError::UnableToCalculateError);
+            }
+            cuts += 1;
+            self.add_cut(row);
+            debug!(
+                "Gomory cut {} from row {}:\n{}",
+                cuts, self.supp_var[row], self
+            );
+            match self.dual_optimise() {
+                Ok(_) => debug!("Table after cut {}:\n{}", cuts, self),
+                // no point satisfies the cuts, so no integer point satisfies the constraints
+                Err(SimplexError::DualUnlimitedError) => {
+                    return Err(SimplexError::NoSolutionsError(None))
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(self.solution())
+    }
+
+    // row with the largest fractional value
+    fn cut_row(&self) -> Option<usize> {
+        let mut choice: Option<(usize, T)> = None;
+        for i in 0..self.table.nrows() - 1 {
+            let fraction = self.fraction(self.free_coeff(i));
+            if !fraction.is_zero() && choice.as_ref().is_none_or(|(_, best)| fraction > *best) {
+                choice = Some((i, fraction));
+            }
+        }
+        choice.map(|(i, _)| i)
+    }
+
+    // the row is y + sum t x = S with integer y and x, so sum frac(t) x >= frac(S),
+    // the cut gets a slack of its own: G = -frac(S) - sum -frac(t) x
+    fn add_cut(&mut self, row: usize) {
+        let name = self.fresh_name("G");
+        let cut: Array1<T> = self.table.row(row).mapv(|i| -self.fraction(i));
+        let fixed: Vec<T> = self
+            .fixed_columns
+            .iter()
+            .map(|(_, column)| -self.fraction(column[row].clone()))
+            .collect();
+        self.insert_row(name, cut);
+        let index = self.table.nrows() - 2;
+        for ((_, column), value) in self.fixed_columns.iter_mut().zip(fixed) {
+            column[index] = value;
+        }
+    }
+
+    // fractional part within [0, 1), round-off around integers counts as zero
+    fn fraction(&self, value: T) -> T {
+        let fraction = value.clone() - floor(value);
+        if fraction <= self.tolerance.feasibility
+            || fraction >= T::one() - self.tolerance.feasibility.clone()
+        {
+            T::zero()
+        } else {
+            fraction
+        }
+    }
+
+    // bounds of integer variables rounded inwards, binary ones kept within zero and one
+    fn root_node(&self) -> Result<Node<T>, SimplexError<T>> {
+        let tolerance = self.tolerance.feasibility.clone();
+        let mut lower_bounds = self.lower_bounds.clone();
+        let mut upper_bounds = self.upper_bounds.clone();
+        for (j, integrality) in self.integrality.iter().enumerate() {
+            if *integrality == Integrality::Continuous {
+                continue;
+            }
+            if *integrality == Integrality::Binary {
+                if lower_bounds[j].as_ref().is_none_or(|i| i.is_negative()) {
+                    lower_bounds[j] = Some(T::zero());
+                }
+                if upper_bounds[j].as_ref().is_none_or(|i| *i > T::one()) {
+                    upper_bounds[j] = Some(T::one());
+                }
+            }
+            lower_bounds[j] = lower_bounds[j]
+                .take()
+                .map(|i| -floor(-i + tolerance.clone()));
+            upper_bounds[j] = upper_bounds[j].take().map(|i| floor(i + tolerance.clone()));
+            if let (Some(lower), Some(upper)) = (lower_bounds[j].clone(), upper_bounds[j].clone()) {
+                if lower > upper {
+                    return Err(SimplexError::NoSolutionsError(None));
+                }
+            }
+        }
+        Ok(Node {
+            lower_bounds,
+            upper_bounds,
+            bound: None,
+            depth: 0,
+        })
+    }
+
+    fn next_node(&self, open: &mut Vec<Node<T>>) -> Option<Node<T>> {
+        match self.node_selection {
+            NodeSelection::DepthFirst => open.pop(),
+            NodeSelection::BestBound => {
+                let mut index = open.len().checked_sub(1)?;
+                for (i, node) in open.iter().enumerate() {
+                    let better = match (node.bound.clone(), open[index].bound.clone()) {
+                        (Some(bound), Some(best)) => self.is_better(bound, best),
+                        (None, Some(_)) => true,
+                        _ => false,
+                    };
+                    if better {
+                        index = i;
+                    }
+                }
+                Some(open.remove(index))
+            }
+        }
+    }
+
+    // integer variable with a fractional value to branch on, None if there is none
+    fn branching_variable(&self, solution: &Solution<T>) -> Option<usize> {
+        let tolerance = self.tolerance.feasibility.clone();
+        let mut choice: Option<(usize, T)> = None;
+        for (j, value) in solution.variables.iter().enumerate() {
+            if self.integrality[j] == Integrality::Continuous {
+                continue;
+            }
+            let fraction = value.clone() - floor(value.clone());
+            if fraction <= tolerance || fraction >= T::one() - tolerance.clone() {
+                continue;
+            }
+            let distance = if fraction.clone() < T::one() - fraction.clone() {
+                fraction
+            } else {
+                T::one() - fraction
+            };
+            match self.branching_rule {
+                BranchingRule::FirstFractional => return Some(j),
+                BranchingRule::MostFractional => {
+                    if choice.as_ref().is_none_or(|(_, best)| distance > *best) {
+                        choice = Some((j, distance));
+                    }
+                }
+            }
+        }
+        choice.map(|(j, _)| j)
+    }
+
+    fn is_better(&self, objective: T, than: T) -> bool {
+        let tolerance = self.tolerance.optimality.clone() * (T::one() + than.abs());
+        if self.minimisation_task {
+            objective < than - tolerance
+        } else {
+            objective > than + tolerance
+        }
+    }
+}
+
+fn floor<T: Scalar>(value: T) -> T {
+    let fraction = value.clone() % T::one();
+    if fraction.is_negative() {
+        value - fraction - T::one()
+    } else {
+        value - fraction
+    }
+}
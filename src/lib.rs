@@ -1,118 +1,773 @@
 use log::debug;
-use ndarray::{Array1, Array2, ArrayView1};
+use ndarray::{s, Array1, Array2, ArrayView1, Axis};
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
 use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+
+mod duality;
+mod iis;
+mod integer;
+mod presolve;
+mod revised;
+mod scalar;
+mod scaling;
+mod sensitivity;
+mod sparse;
+pub use duality::{Certificate, Duality, Farkas, Ray, Violation};
+pub use integer::BranchAndBound;
+pub use presolve::{Postsolve, Reduction};
+pub use scalar::{BigRational, Rational, Scalar};
+pub use sensitivity::Sensitivity;
+pub use sparse::SparseMatrix;
 
 #[derive(Debug)]
-pub enum SimplexError {
+pub enum SimplexError<T = f64> {
     UnableToCalculateError,
-    UnlimitedError,
-    NoSolutionsError,
+    // vertex and direction along which the objective improves without limit, if they were found
+    UnlimitedError(Option<Box<Ray<T>>>),
+    // row multipliers proving the constraints inconsistent, if they were found
+    NoSolutionsError(Option<Farkas<T>>),
     InvalidDataError,
+    // phase one finished with the sum of artificial variables still positive,
+    // the row multipliers prove the constraints inconsistent
+    ArtificialSumError(T, Farkas<T>),
+    // dual simplex found a row that can't be made feasible
+    DualUnlimitedError,
+    // iteration count and the (entering, leaving) pivots of the detected cycle
+    CyclingError(usize, Vec<(String, String)>),
+    // iterations made when max_iterations was reached
+    IterationLimitError(usize),
+    // primal and dual objectives differ by more than the tolerance
+    DualityGapError(T),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feasibility {
+    MakeAcceptable,
+    TwoPhase,
+    BigM,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    // transforms the whole table on every pivot
+    Tableau,
+    // keeps a factorised basis and computes only the pricing row and the entering column
+    Revised,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scaling {
+    None,
+    // rows and columns divided by the geometric mean of their largest and smallest coeffs
+    GeometricMean,
+    // rows and then columns divided by their largest coeff
+    Equilibration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sense {
+    LessEqual,
+    GreaterEqual,
+    Equal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PivotRule {
+    // smallest variable index, never cycles
+    Bland,
+    // largest coeff in the function row
+    Dantzig,
+    // largest change of the function value
+    GreatestImprovement,
+    // largest coeff relative to the length of the edge
+    SteepestEdge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Integrality {
+    Continuous,
+    Integer,
+    // integer between zero and one
+    Binary,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeSelection {
+    // newest node first, finds integer solutions early
+    DepthFirst,
+    // node with the best objective of its parent first, closes the gap fastest
+    BestBound,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BranchingRule {
+    // fractional variable with the smallest index
+    FirstFractional,
+    // variable with the fraction closest to one half
+    MostFractional,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance<T = f64> {
+    // smallest absolute value an element needs to be taken as a pivot
+    pub pivot: T,
+    // function row coeffs not greater than it count as optimal
+    pub optimality: T,
+    // free coeffs not less than its negative count as feasible
+    pub feasibility: T,
+}
+
+impl<T: Scalar> Default for Tolerance<T> {
+    fn default() -> Tolerance<T> {
+        Tolerance {
+            pivot: T::default_tolerance(),
+            optimality: T::default_tolerance(),
+            feasibility: T::default_tolerance(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution<T = f64> {
+    pub objective: T,
+    // values of the decision variables, non-basic ones are zero
+    pub variables: Vec<T>,
+    // values of the slack variables of the constraints
+    pub slacks: Vec<T>,
+    // names matching the values above
+    pub variable_names: Vec<String>,
+    pub constraint_names: Vec<String>,
+    // basic variables in the order of the table rows
+    pub basis: Vec<String>,
+    // decision variables sitting at their upper bounds
+    pub at_upper: Vec<String>,
+    pub iterations: usize,
 }
 
-pub struct Table {
-    pub table: Array2<f64>,
+impl<T: Scalar> Solution<T> {
+    // looks up a decision variable or a constraint slack by name
+    pub fn value(&self, name: &str) -> Option<T> {
+        if let Some(i) = self.variable_names.iter().position(|i| i == name) {
+            return Some(self.variables[i].clone());
+        }
+        let i = self.constraint_names.iter().position(|i| i == name)?;
+        Some(self.slacks[i].clone())
+    }
+}
+
+// decision variable expressed through the non-negative columns of the table:
+// value = offset + positive - negative
+#[derive(Debug, Clone)]
+struct Substitution<T> {
+    offset: T,
+    positive: Option<String>,
+    negative: Option<String>,
+}
+
+// what stops the entering variable in the ratio test
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Leaving {
+    // basic variable of the row drops to zero
+    Row(usize),
+    // basic variable of the row reaches its upper bound
+    UpperRow(usize),
+    // entering variable reaches its own upper bound
+    Flip,
+}
+
+pub struct Table<T = f64> {
+    // empty while the table is kept in compressed columns, read it with table()
+    table: Array2<T>,
+    // the same table in compressed columns, used instead of the dense one
+    // until a method working on the whole table needs it
+    sparse: Option<SparseMatrix<T>>,
     pub base_var: Vec<String>,
     pub supp_var: Vec<String>,
+    pub feasibility: Feasibility,
+    pub algorithm: Algorithm,
+    pub scaling: Scaling,
+    // objective row of artificial variables, transformed along with the table
+    pub penalty: Option<Array1<T>>,
+    pub artificial: Vec<String>,
+    pub pivot_rule: PivotRule,
+    pub iterations: usize,
+    pub max_iterations: Option<usize>,
+    pub node_selection: NodeSelection,
+    pub branching_rule: BranchingRule,
+    // branch and bound stops with the best solution found after solving that many nodes
+    pub max_nodes: Option<usize>,
+    // Gomory cuts give up after adding that many rows
+    pub max_cuts: Option<usize>,
+    pub tolerance: Tolerance<T>,
+    pub minimisation_task: bool,
+    // names of the decision variables and of the slack variables of the constraints
+    pub variables: Vec<String>,
+    pub constraints: Vec<String>,
+    pub senses: Vec<Sense>,
+    // lower bound of every decision variable, None for a free one
+    pub lower_bounds: Vec<Option<T>>,
+    pub upper_bounds: Vec<Option<T>>,
+    // used by branch and bound and Gomory cuts, optimise solves the continuous task
+    pub integrality: Vec<Integrality>,
+    substitutions: Vec<Substitution<T>>,
+    // upper bounds of the table columns after substitution
+    upper: HashMap<String, T>,
+    // variables replaced with their complement to the upper bound
+    at_upper: Vec<String>,
+    // columns of the equality slacks fixed at zero, they are kept out of the table
+    // but transformed along with it for the sensitivity report
+    fixed_columns: Vec<(String, Array1<T>)>,
+    // factors the constraints and the decision variables were multiplied by,
+    // empty while the table is not scaled
+    row_scale: Vec<T>,
+    column_scale: Vec<T>,
+    // hashes of the visited bases with the number of pivots made before reaching them
+    history: HashMap<u64, usize>,
+    pivots: Vec<(String, String)>,
+    // every variable name in the order of their indices, used by Bland's rule
+    var_order: Vec<String>,
 }
 
-impl Display for Table {
+impl<T: Scalar> Display for Table<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let table = match &self.sparse {
+            Some(sparse) => return self.fmt_sparse(f, sparse),
+            None => &self.table,
+        };
         for i in self.base_var.iter() {
-            write!(f, "\t| {}\t", i)?;
+            write!(f, "\t| {}\t", self.label(i))?;
         }
-        write!(f, "\n")?;
-        for i in 0..self.table.nrows() {
-            write!(f, "{}", self.supp_var[i])?;
-            for j in self.table.row(i).iter() {
-                write!(f, "\t| {:.7}", j)?;
+        writeln!(f)?;
+        for i in 0..table.nrows() {
+            write!(f, "{}", self.label(&self.supp_var[i]))?;
+            if i == table.nrows() - 1 && self.feasibility == Feasibility::BigM {
+                if let Some(penalty) = &self.penalty {
+                    for (j, m) in table.row(i).iter().zip(penalty.iter()) {
+                        write!(f, "\t| ")?;
+                        fmt_big_m(f, j.clone(), m.clone())?;
+                    }
+                    writeln!(f)?;
+                    continue;
+                }
             }
-            write!(f, "\n")?;
+            for j in table.row(i).iter() {
+                write!(f, "\t| ")?;
+                j.fmt_entry(f)?;
+            }
+            writeln!(f)?;
+        }
+        if let (Some(penalty), Feasibility::TwoPhase) = (&self.penalty, self.feasibility) {
+            write!(f, "W")?;
+            for j in penalty.iter() {
+                write!(f, "\t| ")?;
+                j.fmt_entry(f)?;
+            }
+            writeln!(f)?;
         }
-        writeln!(f, "")?; // empty line
+        writeln!(f)?; // empty line
         self.print_function(f)?;
         Ok(())
     }
 }
 
-impl Clone for Table {
-    fn clone(&self) -> Table {
+impl<T: Scalar> Clone for Table<T> {
+    fn clone(&self) -> Table<T> {
         Table {
             table: self.table.clone(),
+            sparse: self.sparse.clone(),
             base_var: self.base_var.clone(),
             supp_var: self.supp_var.clone(),
+            feasibility: self.feasibility,
+            algorithm: self.algorithm,
+            scaling: self.scaling,
+            penalty: self.penalty.clone(),
+            artificial: self.artificial.clone(),
+            pivot_rule: self.pivot_rule,
+            iterations: self.iterations,
+            max_iterations: self.max_iterations,
+            node_selection: self.node_selection,
+            branching_rule: self.branching_rule,
+            max_nodes: self.max_nodes,
+            max_cuts: self.max_cuts,
+            tolerance: self.tolerance.clone(),
+            minimisation_task: self.minimisation_task,
+            variables: self.variables.clone(),
+            constraints: self.constraints.clone(),
+            senses: self.senses.clone(),
+            lower_bounds: self.lower_bounds.clone(),
+            upper_bounds: self.upper_bounds.clone(),
+            integrality: self.integrality.clone(),
+            upper: self.upper.clone(),
+            at_upper: self.at_upper.clone(),
+            fixed_columns: self.fixed_columns.clone(),
+            row_scale: self.row_scale.clone(),
+            column_scale: self.column_scale.clone(),
+            substitutions: self.substitutions.clone(),
+            history: self.history.clone(),
+            pivots: self.pivots.clone(),
+            var_order: self.var_order.clone(),
         }
     }
 }
 
-impl Table {
+impl<T: Scalar> Table<T> {
     pub fn new(
-        mut constr_coeff: Array2<f64>,
-        constr_val: Array1<f64>,
-        mut func_coeff: Array1<f64>,
+        mut constr_coeff: Array2<T>,
+        constr_val: Array1<T>,
+        mut func_coeff: Array1<T>,
         minimisation_task: bool,
-    ) -> Table {
+    ) -> Result<Table<T>, SimplexError<T>> {
+        if constr_val.len() != constr_coeff.nrows() || func_coeff.len() != constr_coeff.ncols() {
+            return Err(SimplexError::InvalidDataError);
+        }
         if minimisation_task {
             for i in func_coeff.iter_mut() {
-                *i *= -1f64;
+                *i = -i.clone();
             }
         }
-        constr_coeff.push_row(ArrayView1::from(&func_coeff));
-        let mut constr_val_vec = constr_val.to_vec();
-        while constr_val_vec.len() < constr_coeff.nrows() {
-            constr_val_vec.push(0f64);
-        }
-        let constr_val = Array1::from_vec(constr_val_vec);
+        constr_coeff
+            .push_row(ArrayView1::from(&func_coeff))
+            .map_err(|_| SimplexError::InvalidDataError)?;
+        // the free coeff of the function row starts at zero
+        let mut constr_val = constr_val.to_vec();
+        constr_val.push(T::zero());
+        let constr_val = Array1::from_vec(constr_val);
         constr_coeff
             .push_column(ArrayView1::from(&constr_val))
-            .unwrap();
-        let mut base_var = Vec::<String>::with_capacity(constr_coeff.ncols() - 1);
-        for i in 1..constr_coeff.ncols() {
+            .map_err(|_| SimplexError::InvalidDataError)?;
+        let shape = (constr_coeff.nrows(), constr_coeff.ncols());
+        Ok(Table::with_table(
+            constr_coeff,
+            None,
+            shape,
+            minimisation_task,
+        ))
+    }
+
+    // same as new, but the constraint matrix is kept sparse
+    pub fn from_sparse(
+        constr_coeff: SparseMatrix<T>,
+        constr_val: Array1<T>,
+        func_coeff: Array1<T>,
+        minimisation_task: bool,
+    ) -> Result<Table<T>, SimplexError<T>> {
+        let (nrows, ncols) = (constr_coeff.nrows(), constr_coeff.ncols());
+        if constr_val.len() != nrows || func_coeff.len() != ncols {
+            return Err(SimplexError::InvalidDataError);
+        }
+        let mut table = SparseMatrix::new(nrows + 1);
+        for j in 0..ncols {
+            let func = if minimisation_task {
+                -func_coeff[j].clone()
+            } else {
+                func_coeff[j].clone()
+            };
+            table.push_column(constr_coeff.column(j).chain(std::iter::once((nrows, func))));
+        }
+        table.push_column(constr_val.iter().cloned().enumerate());
+        let mut table = Table::with_table(
+            Array2::zeros((0, 0)),
+            Some(table),
+            (nrows + 1, ncols + 1),
+            minimisation_task,
+        );
+        // the tableau method would make the whole table dense
+        table.algorithm = Algorithm::Revised;
+        Ok(table)
+    }
+
+    // the current table with the function row last and the free column on the right,
+    // a sparse table is copied into a dense one
+    pub fn table(&self) -> Cow<'_, Array2<T>> {
+        match &self.sparse {
+            Some(sparse) => Cow::Owned(sparse.to_dense()),
+            None => Cow::Borrowed(&self.table),
+        }
+    }
+
+    fn with_table(
+        table: Array2<T>,
+        sparse: Option<SparseMatrix<T>>,
+        (nrows, ncols): (usize, usize),
+        minimisation_task: bool,
+    ) -> Table<T> {
+        let mut base_var = Vec::<String>::with_capacity(ncols - 1);
+        for i in 1..ncols {
             base_var.push(i.to_string());
         }
         base_var.push("S".to_string());
-        let mut supp_var = Vec::<String>::with_capacity(constr_coeff.nrows());
-        for i in 0..constr_coeff.nrows() - 1 {
-            supp_var.push((i + constr_coeff.ncols()).to_string());
+        let mut supp_var = Vec::<String>::with_capacity(nrows);
+        for i in 0..nrows - 1 {
+            supp_var.push((i + ncols).to_string());
         }
         supp_var.push("F".to_string());
+        let variables = base_var[..base_var.len() - 1].to_vec();
+        let constraints = supp_var[..supp_var.len() - 1].to_vec();
+        let senses = vec![Sense::LessEqual; constraints.len()];
+        let lower_bounds = vec![Some(T::zero()); variables.len()];
+        let upper_bounds = vec![None; variables.len()];
+        let integrality = vec![Integrality::Continuous; variables.len()];
+        let mut var_order = variables.clone();
+        var_order.extend_from_slice(&constraints);
         Table {
-            table: constr_coeff,
+            table,
+            sparse,
             base_var,
             supp_var,
+            feasibility: Feasibility::MakeAcceptable,
+            algorithm: Algorithm::Tableau,
+            scaling: Scaling::None,
+            penalty: None,
+            artificial: Vec::new(),
+            pivot_rule: PivotRule::Bland,
+            iterations: 0,
+            max_iterations: None,
+            node_selection: NodeSelection::DepthFirst,
+            branching_rule: BranchingRule::MostFractional,
+            max_nodes: None,
+            max_cuts: None,
+            tolerance: Tolerance::default(),
+            minimisation_task,
+            variables,
+            constraints,
+            senses,
+            lower_bounds,
+            upper_bounds,
+            integrality,
+            upper: HashMap::new(),
+            at_upper: Vec::new(),
+            fixed_columns: Vec::new(),
+            row_scale: Vec::new(),
+            column_scale: Vec::new(),
+            substitutions: Vec::new(),
+            history: HashMap::new(),
+            pivots: Vec::new(),
+            var_order,
+        }
+    }
+    // replaces the default numbered names of decision variables and constraints,
+    // this has to be done before optimising
+    pub fn set_names(
+        &mut self,
+        variables: &[&str],
+        constraints: &[&str],
+    ) -> Result<(), crate::SimplexError<T>> {
+        if variables.len() != self.variables.len()
+            || constraints.len() != self.constraints.len()
+            || !self.substitutions.is_empty()
+        {
+            return Err(SimplexError::InvalidDataError);
+        }
+        // S and F label the free column and the function row
+        if variables
+            .iter()
+            .chain(constraints.iter())
+            .any(|name| *name == "S" || *name == "F")
+        {
+            return Err(SimplexError::InvalidDataError);
+        }
+        let mut names: Vec<&str> = variables
+            .iter()
+            .chain(constraints.iter())
+            .copied()
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        if names.len() != variables.len() + constraints.len() {
+            return Err(SimplexError::InvalidDataError);
         }
+        let renames = self
+            .variables
+            .iter()
+            .zip(variables.iter())
+            .chain(self.constraints.iter().zip(constraints.iter()))
+            .map(|(old, new)| (old.clone(), new.to_string()))
+            .collect::<HashMap<String, String>>();
+        for name in self
+            .base_var
+            .iter_mut()
+            .chain(self.supp_var.iter_mut())
+            .chain(self.var_order.iter_mut())
+            .chain(self.variables.iter_mut())
+            .chain(self.constraints.iter_mut())
+        {
+            if let Some(new) = renames.get(name) {
+                *name = new.clone();
+            }
+        }
+        Ok(())
     }
-    pub fn optimise(&mut self) -> Result<(), crate::SimplexError> {
+
+    // constraints are built as <= by default, this has to be done before optimising
+    pub fn set_senses(&mut self, senses: &[Sense]) -> Result<(), crate::SimplexError<T>> {
+        if senses.len() != self.senses.len() || self.iterations != 0 {
+            return Err(SimplexError::InvalidDataError);
+        }
+        for (i, sense) in senses.iter().enumerate() {
+            // >= rows are negated, so their slack becomes a surplus variable
+            if (*sense == Sense::GreaterEqual) != (self.senses[i] == Sense::GreaterEqual) {
+                self.negate_row(i);
+            }
+            self.senses[i] = *sense;
+        }
+        Ok(())
+    }
+
+    pub fn optimise(&mut self) -> Result<Solution<T>, crate::SimplexError<T>> {
+        self.scale();
+        self.substitute_bounds()?;
         debug!("Beginning table:\n{}", self);
-        loop {
-            let negative_row = self.find_in_free_column();
-            if negative_row.is_none() {
-                break;
+        if self.algorithm == Algorithm::Revised {
+            return self.revised_optimise();
+        }
+        self.densify();
+        self.reset_history(self.basis_hash());
+        match self.feasibility {
+            Feasibility::MakeAcceptable => {
+                self.eliminate_equalities()?;
+                loop {
+                    let negative_row = self.find_in_free_column();
+                    if negative_row.is_none() {
+                        break;
+                    }
+                    let negative_row = negative_row.unwrap();
+                    self.complement_above_upper(negative_row);
+                    debug!("Found negative free coeff in row {}", negative_row);
+                    self.make_acceptable(negative_row)?;
+                    debug!("Making acceptable:\n{}", self);
+                }
             }
-            let negative_row = negative_row.unwrap();
-            debug!("Found negative free coeff in row {}", negative_row);
-            self.make_acceptable(negative_row)?;
-            debug!("Making acceptable:\n{}", self);
+            Feasibility::TwoPhase => self.phase_one()?,
+            Feasibility::BigM => self.big_m()?,
         }
         // begin transformation
         while !self.check_optimised() {
             self.iterate()?;
             debug!("Iteration:\n{}\n", self);
         }
+        Ok(self.solution())
+    }
+
+    // expects the function row to be optimal already and restores feasibility of the free column
+    pub fn dual_optimise(&mut self) -> Result<Solution<T>, crate::SimplexError<T>> {
+        self.scale();
+        self.substitute_bounds()?;
+        self.densify();
+        debug!("Beginning dual table:\n{}", self);
+        self.reset_history(self.basis_hash());
+        self.eliminate_equalities()?;
+        if !self.check_optimised() {
+            return Err(SimplexError::InvalidDataError);
+        }
+        while let Some(i) = self.find_in_free_column() {
+            self.complement_above_upper(i);
+            let j = self
+                .find_dual_pivot_column(i)
+                .ok_or(SimplexError::DualUnlimitedError)?;
+            debug!("Dual pivot: i: {}\tj: {}", i, j);
+            self.pivot((i, j))?;
+            debug!("Dual iteration:\n{}\n", self);
+        }
+        Ok(self.solution())
+    }
+
+    // adds sum of coeffs * decision variables (sense) value to an optimised table,
+    // the row is written in the current basis and the dual simplex goes on from there
+    pub fn add_constraint(
+        &mut self,
+        coeffs: &[T],
+        sense: Sense,
+        value: T,
+    ) -> Result<Solution<T>, crate::SimplexError<T>> {
+        if self.substitutions.is_empty()
+            || coeffs.len() != self.variables.len()
+            || !self.artificial.is_empty()
+        {
+            return Err(SimplexError::InvalidDataError);
+        }
+        self.densify();
+        if !self.check_optimised() {
+            return Err(SimplexError::InvalidDataError);
+        }
+        let (nrows, ncols) = (self.table.nrows(), self.table.ncols());
+        // the constraint sum is value - sum of coeff * column variable, see expression
+        let mut expression = Array1::<T>::zeros(ncols);
+        let mut fixed = vec![T::zero(); self.fixed_columns.len()];
+        for (j, coeff) in coeffs.iter().enumerate() {
+            if coeff.is_zero() {
+                continue;
+            }
+            let coeff = coeff.clone() * self.column_factor(j);
+            for (k, value) in self.variable_expression(j).iter().enumerate() {
+                expression[k] += coeff.clone() * value.clone();
+            }
+            let substitution = &self.substitutions[j];
+            let parts = [
+                (&substitution.positive, coeff.clone()),
+                (&substitution.negative, -coeff),
+            ];
+            for (name, coeff) in parts {
+                let Some(name) = name else {
+                    continue;
+                };
+                let Some(i) = self.supp_var[..nrows - 1].iter().position(|i| i == name) else {
+                    continue;
+                };
+                let coeff = if self.at_upper.contains(name) {
+                    -coeff
+                } else {
+                    coeff
+                };
+                for (k, (_, column)) in self.fixed_columns.iter().enumerate() {
+                    fixed[k] += coeff.clone() * column[i].clone();
+                }
+            }
+        }
+        // the slack is value - sum, >= rows are negated so that it becomes a surplus
+        let direction = if sense == Sense::GreaterEqual {
+            -T::one()
+        } else {
+            T::one()
+        };
+        let mut row = expression.mapv(|i| -direction.clone() * i);
+        row[ncols - 1] = direction.clone() * (value - expression[ncols - 1].clone());
+        let name = self.fresh_name("");
+        self.constraints.push(name.clone());
+        self.senses.push(sense);
+        if !self.row_scale.is_empty() {
+            self.row_scale.push(T::one());
+        }
+        self.insert_row(name.clone(), row);
+        for ((_, column), value) in self.fixed_columns.iter_mut().zip(fixed) {
+            column[nrows - 1] = -direction.clone() * value;
+        }
+        debug!("Added constraint {}:\n{}", name, self);
+        if sense == Sense::Equal {
+            self.fix_equality(nrows - 1)?;
+        }
+        self.dual_optimise()
+    }
+
+    // the zero slack of a new equality leaves the basis on the column that keeps the function
+    // row optimal, then its column is fixed like in eliminate_equalities
+    fn fix_equality(&mut self, row: usize) -> Result<(), SimplexError<T>> {
+        let (last_row, last) = (self.table.nrows() - 1, self.table.ncols() - 1);
+        let value = self.table[[row, last]].clone();
+        let mut choice: Option<(usize, T)> = None;
+        for j in 0..last {
+            let coeff = self.table[[row, j]].clone();
+            // the entering variable takes value / coeff, which can't be negative
+            let fits = if value > self.tolerance.feasibility {
+                coeff > self.tolerance.pivot
+            } else if value < -self.tolerance.feasibility.clone() {
+                coeff < -self.tolerance.pivot.clone()
+            } else {
+                coeff.abs() > self.tolerance.pivot
+            };
+            if !fits {
+                continue;
+            }
+            let ratio = (self.table[[last_row, j]].clone() / coeff).abs();
+            if choice.as_ref().is_none_or(|(_, best)| ratio < *best) {
+                choice = Some((j, ratio));
+            }
+        }
+        match choice {
+            Some((j, _)) => {
+                debug!(
+                    "Fixing equality {} on pivot i: {}\tj: {}",
+                    self.supp_var[row], row, j
+                );
+                self.fix_column(row, j)?;
+            }
+            None if value.abs() <= self.tolerance.feasibility => {
+                debug!("Removing redundant row {}", row);
+                self.remove_row(row);
+            }
+            None => return Err(SimplexError::DualUnlimitedError),
+        }
         Ok(())
     }
 
+    // reads the basic solution of the current table
+    pub fn solution(&self) -> Solution<T> {
+        let last = self.shape().0 - 1;
+        let value = |name: &String| {
+            let value = self.supp_var[..last]
+                .iter()
+                .position(|i| i == name)
+                .map_or(T::zero(), |i| self.free_coeff(i));
+            if self.at_upper.contains(name) {
+                self.upper[name].clone() - value
+            } else {
+                value
+            }
+        };
+        let variables: Vec<T> = if self.substitutions.is_empty() {
+            self.variables.iter().map(value).collect()
+        } else {
+            self.substitutions
+                .iter()
+                .map(|i| {
+                    let positive = i.positive.as_ref().map_or(T::zero(), value);
+                    let negative = i.negative.as_ref().map_or(T::zero(), value);
+                    i.offset.clone() + positive - negative
+                })
+                .collect()
+        };
+        let variables: Vec<T> = variables
+            .into_iter()
+            .enumerate()
+            .map(|(j, i)| i * self.column_factor(j))
+            .collect();
+        let at_upper = self
+            .variables
+            .iter()
+            .zip(variables.iter().zip(self.upper_bounds.iter()))
+            .filter(|(_, (value, upper))| {
+                upper.as_ref().is_some_and(|upper| {
+                    ((*value).clone() - upper.clone()).abs() <= self.tolerance.feasibility
+                })
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        // the function row holds the value of the minimised function
+        let objective = self.free_coeff(last);
+        Solution {
+            objective: if self.minimisation_task {
+                objective
+            } else {
+                -objective
+            },
+            variables,
+            slacks: self
+                .constraints
+                .iter()
+                .enumerate()
+                .map(|(i, name)| value(name) / self.row_factor(i))
+                .collect(),
+            variable_names: self.variables.clone(),
+            constraint_names: self.constraints.clone(),
+            basis: self.supp_var[..last].to_vec(),
+            at_upper,
+            iterations: self.iterations,
+        }
+    }
+
     fn check_optimised(&self) -> bool {
         for i in self.table.row(self.table.nrows() - 1).iter().enumerate() {
             // don't check for the last column (with free coeffs)
             if i.0 >= self.table.ncols() - 1 {
                 break;
             }
-            if i.1.is_sign_positive() {
+            if *i.1 > self.tolerance.optimality {
                 return false;
             }
         }
-        return true;
+        true
     }
 
     fn find_in_free_column(&self) -> Option<usize> {
@@ -121,14 +776,19 @@ impl Table {
             if i.0 >= self.table.nrows() - 1 {
                 break;
             }
-            if i.1.is_sign_negative() {
+            if *i.1 < -self.tolerance.feasibility.clone() {
                 return Some(i.0);
             }
+            if let Some(upper) = self.upper.get(&self.supp_var[i.0]) {
+                if *i.1 > upper.clone() + self.tolerance.feasibility.clone() {
+                    return Some(i.0);
+                }
+            }
         }
-        return None;
+        None
     }
 
-    fn make_acceptable(&mut self, negative: usize) -> Result<(), SimplexError> {
+    fn make_acceptable(&mut self, negative: usize) -> Result<(), SimplexError<T>> {
         let j = {
             let mut index = None;
             for i in self.table.row(negative).iter().enumerate() {
@@ -136,109 +796,933 @@ impl Table {
                 if i.0 >= self.table.ncols() - 1 {
                     break;
                 }
-                if i.1.is_sign_negative() {
+                if *i.1 < -self.tolerance.pivot.clone() {
                     index = Some(i.0);
                     break;
                 }
             }
             if index.is_none() {
-                return Err(SimplexError::NoSolutionsError);
+                return Err(SimplexError::NoSolutionsError(Some(self.farkas(negative))));
             }
             index.unwrap()
         };
-        if let Some(i) = self.find_pivot_row(j) {
-            debug!("Transforming on pivot i: {}\tj: {}", i, j);
-            self.transform((i, j));
-            std::mem::swap(&mut self.base_var[j], &mut self.supp_var[i]);
-            return Ok(());
+        if let Some(leaving) = self.find_pivot_row(j) {
+            debug!("Transforming on pivot {:?}\tj: {}", leaving, j);
+            self.step(j, leaving)
         } else {
-            return Err(SimplexError::UnableToCalculateError);
+            Err(SimplexError::UnableToCalculateError)
         }
     }
 
-    fn iterate(&mut self) -> Result<(), SimplexError> {
-        let (i, j) = self.find_pivot()?;
-        debug!("Current pivot: i: {}\tj:{}", i, j);
-        self.transform((i, j));
-        std::mem::swap(&mut self.base_var[j], &mut self.supp_var[i]);
+    fn phase_one(&mut self) -> Result<(), SimplexError<T>> {
+        self.add_artificial();
+        if self.artificial.is_empty() {
+            return Ok(());
+        }
+        debug!("Phase one table:\n{}", self);
+        while let Some(j) = self.find_penalty_column() {
+            let leaving = self
+                .find_pivot_row(j)
+                .ok_or(SimplexError::UnableToCalculateError)?;
+            debug!("Phase one pivot: {:?}\tj: {}", leaving, j);
+            self.step(j, leaving)?;
+            debug!("Phase one iteration:\n{}\n", self);
+        }
+        self.finish_artificial()?;
+        debug!("Phase one finished:\n{}", self);
         Ok(())
     }
 
-    fn find_pivot(&self) -> Result<(usize, usize), SimplexError> {
-        let j = {
-            let mut index = None;
-            for j in self.table.row(self.table.nrows() - 1).iter().enumerate() {
-                if j.1.is_sign_positive() {
-                    index = Some(j.0);
-                    break;
+    fn big_m(&mut self) -> Result<(), SimplexError<T>> {
+        self.add_artificial();
+        if self.artificial.is_empty() {
+            return Ok(());
+        }
+        debug!("Big-M table:\n{}", self);
+        while let Some(j) = self.find_big_m_column()? {
+            // the vertex is not feasible yet, so there is no ray to show
+            let leaving = self
+                .find_pivot_row(j)
+                .ok_or(SimplexError::UnlimitedError(None))?;
+            debug!("Big-M pivot: {:?}\tj: {}", leaving, j);
+            self.step(j, leaving)?;
+            debug!("Big-M iteration:\n{}\n", self);
+        }
+        self.finish_artificial()?;
+        debug!("Big-M finished:\n{}", self);
+        Ok(())
+    }
+
+    fn finish_artificial(&mut self) -> Result<(), SimplexError<T>> {
+        self.check_artificial_sum()?;
+        self.drop_artificial()?;
+        // round-off on degenerate rows must not make the basis look infeasible
+        let last = self.table.ncols() - 1;
+        for i in self.table.column_mut(last) {
+            if i.abs() <= self.tolerance.feasibility {
+                *i = T::zero();
+            }
+        }
+        Ok(())
+    }
+
+    fn check_artificial_sum(&self) -> Result<(), SimplexError<T>> {
+        let sum = self.penalty.as_ref().unwrap()[self.table.ncols() - 1].clone();
+        if sum > self.tolerance.feasibility {
+            return Err(SimplexError::ArtificialSumError(
+                sum,
+                self.phase_one_farkas(),
+            ));
+        }
+        Ok(())
+    }
+
+    // replaces every basic variable with negative free coeff by an artificial one
+    // and builds the objective row for the sum of artificial variables
+    fn add_artificial(&mut self) {
+        for i in 0..self.table.nrows() - 1 {
+            self.complement_above_upper(i);
+            let equality = self.is_equality(&self.supp_var[i]);
+            let negative =
+                self.table[[i, self.table.ncols() - 1]] < -self.tolerance.feasibility.clone();
+            if !equality && !negative {
+                continue;
+            }
+            if negative {
+                for j in self.table.row_mut(i) {
+                    *j = -j.clone();
                 }
             }
-            if index.is_none() {
-                return Err(SimplexError::UnableToCalculateError);
+            let name = self.fresh_name("A");
+            let slack = std::mem::replace(&mut self.supp_var[i], name.clone());
+            let mut column = Array1::<T>::zeros(self.table.nrows());
+            column[i] = if negative { -T::one() } else { T::one() };
+            // slack of an equality is zero, so it has no column in the table
+            if equality {
+                self.fixed_columns.push((slack, column));
+            } else {
+                self.insert_column(slack, column);
             }
-            index.unwrap()
+            self.var_order.push(name.clone());
+            self.artificial.push(name);
+        }
+        if self.artificial.is_empty() {
+            return;
+        }
+        let mut penalty = Array1::<T>::zeros(self.table.ncols());
+        for i in 0..self.table.nrows() - 1 {
+            if self.artificial.contains(&self.supp_var[i]) {
+                penalty += &self.table.row(i);
+            }
+        }
+        self.penalty = Some(penalty);
+    }
+
+    // moves lower bounds to zero and splits free variables into positive and negative parts,
+    // done once before the first pivot
+    fn substitute_bounds(&mut self) -> Result<(), SimplexError<T>> {
+        if !self.substitutions.is_empty() {
+            return Ok(());
+        }
+        if self.lower_bounds.len() != self.variables.len()
+            || self.upper_bounds.len() != self.variables.len()
+        {
+            return Err(SimplexError::InvalidDataError);
+        }
+        // bounds of a scaled column are divided by its factor
+        let bounds: Vec<(Option<T>, Option<T>)> = (0..self.variables.len())
+            .map(|j| {
+                let factor = self.column_factor(j);
+                (
+                    self.lower_bounds[j].clone().map(|i| i / factor.clone()),
+                    self.upper_bounds[j].clone().map(|i| i / factor),
+                )
+            })
+            .collect();
+        for (name, bound) in self.variables.clone().iter().zip(bounds) {
+            let j = self
+                .base_var
+                .iter()
+                .position(|i| i == name)
+                .ok_or(SimplexError::InvalidDataError)?;
+            let substitution = match bound {
+                (Some(lower), upper) => {
+                    if upper.as_ref().is_some_and(|upper| *upper < lower) {
+                        return Err(SimplexError::NoSolutionsError(None));
+                    }
+                    let positive = if lower.is_zero() {
+                        name.clone()
+                    } else {
+                        // x = lower + x'
+                        self.shift_column(j, lower.clone());
+                        let positive = self.split_name(name, "'");
+                        self.rename(name, &positive);
+                        positive
+                    };
+                    if let Some(upper) = upper {
+                        self.upper.insert(positive.clone(), upper - lower.clone());
+                    }
+                    Substitution {
+                        offset: lower,
+                        positive: Some(positive),
+                        negative: None,
+                    }
+                }
+                (None, Some(upper)) => {
+                    // x = upper - x'
+                    self.shift_column(j, upper.clone());
+                    self.negate_column(j);
+                    let negative = self.split_name(name, "'");
+                    self.rename(name, &negative);
+                    Substitution {
+                        offset: upper,
+                        positive: None,
+                        negative: Some(negative),
+                    }
+                }
+                (None, None) => {
+                    // x = x+ - x-
+                    let positive = self.split_name(name, "+");
+                    let negative = self.split_name(name, "-");
+                    let mut column = Array1::zeros(self.shape().0);
+                    for (i, value) in self.table_column(j) {
+                        column[i] = -value;
+                    }
+                    self.rename(name, &positive);
+                    self.insert_column(negative.clone(), column);
+                    self.var_order.push(negative.clone());
+                    Substitution {
+                        offset: T::zero(),
+                        positive: Some(positive),
+                        negative: Some(negative),
+                    }
+                }
+            };
+            self.substitutions.push(substitution);
+        }
+        debug!("Columns {:?} have upper bounds", self.upper);
+        Ok(())
+    }
+
+    // moves the free coeffs as if the variable of the column was set to value
+    fn shift_column(&mut self, column: usize, value: T) {
+        let (nrows, ncols) = self.shape();
+        let last = ncols - 1;
+        if let Some(sparse) = self.sparse.as_mut() {
+            let mut free = vec![T::zero(); nrows];
+            for (i, coeff) in sparse.pop_column() {
+                free[i] = coeff;
+            }
+            for (i, coeff) in sparse.column(column) {
+                free[i] -= coeff * value.clone();
+            }
+            sparse.push_column(free.into_iter().enumerate());
+        } else {
+            for i in 0..nrows {
+                let delta = self.table[[i, column]].clone() * value.clone();
+                self.table[[i, last]] -= delta;
+            }
+        }
+        if let Some(penalty) = self.penalty.as_mut() {
+            let delta = penalty[column].clone() * value;
+            penalty[last] -= delta;
+        }
+    }
+
+    // replaces the non-basic variable of the column at zero with its complement to the upper bound,
+    // so the variable moves to the bound
+    fn complement_column(&mut self, column: usize) {
+        let name = self.base_var[column].clone();
+        let upper = self.upper[&name].clone();
+        self.shift_column(column, upper);
+        self.negate_column(column);
+        if let Some(penalty) = self.penalty.as_mut() {
+            penalty[column] = -penalty[column].clone();
+        }
+        self.toggle_at_upper(name);
+    }
+
+    // replaces the basic variable of the row with its complement to the upper bound
+    fn complement_row(&mut self, row: usize) {
+        let name = self.supp_var[row].clone();
+        let upper = self.upper[&name].clone();
+        self.negate_row(row);
+        let last = self.shape().1 - 1;
+        match self.sparse.as_mut() {
+            Some(sparse) => sparse.add(row, last, upper),
+            None => self.table[[row, last]] += upper,
+        }
+        self.toggle_at_upper(name);
+    }
+
+    // (rows, columns) of the table, wherever it is stored
+    fn shape(&self) -> (usize, usize) {
+        match &self.sparse {
+            Some(sparse) => (sparse.nrows(), sparse.ncols()),
+            None => (self.table.nrows(), self.table.ncols()),
+        }
+    }
+
+    // non-zero entries of the table column in increasing row order
+    fn table_column(&self, column: usize) -> Vec<(usize, T)> {
+        match &self.sparse {
+            Some(sparse) => sparse.column(column).collect(),
+            None => self
+                .table
+                .column(column)
+                .iter()
+                .cloned()
+                .enumerate()
+                .filter(|(_, value)| !value.is_zero())
+                .collect(),
+        }
+    }
+
+    fn free_coeff(&self, row: usize) -> T {
+        self.entry(row, self.shape().1 - 1)
+    }
+
+    fn entry(&self, row: usize, column: usize) -> T {
+        match &self.sparse {
+            Some(sparse) => sparse.get(row, column),
+            None => self.table[[row, column]].clone(),
+        }
+    }
+
+    // a variable of the table as value - sum of coeff * column variable,
+    // the value is kept in the last element
+    fn expression(&self, name: &String) -> Array1<T> {
+        let (nrows, ncols) = self.shape();
+        let mut expression = Array1::zeros(ncols);
+        let upper = self.upper.get(name).cloned().unwrap_or(T::zero());
+        let complemented = self.at_upper.contains(name);
+        if let Some(i) = self.supp_var[..nrows - 1].iter().position(|i| i == name) {
+            for j in 0..ncols {
+                expression[j] = self.entry(i, j);
+            }
+            if complemented {
+                expression.mapv_inplace(|i| -i);
+                expression[ncols - 1] += upper;
+            }
+        } else if let Some(j) = self.base_var[..ncols - 1].iter().position(|i| i == name) {
+            if complemented {
+                expression[j] = T::one();
+                expression[ncols - 1] = upper;
+            } else {
+                expression[j] = -T::one();
+            }
+        }
+        expression
+    }
+
+    // decision variable of the task in terms of the column variables, see expression
+    fn variable_expression(&self, index: usize) -> Array1<T> {
+        let substitution = &self.substitutions[index];
+        let mut expression = Array1::zeros(self.shape().1);
+        if let Some(positive) = &substitution.positive {
+            expression += &self.expression(positive);
+        }
+        if let Some(negative) = &substitution.negative {
+            expression -= &self.expression(negative);
+        }
+        expression[self.shape().1 - 1] += substitution.offset.clone();
+        expression
+    }
+
+    // methods transforming the table in place work on the dense array
+    fn densify(&mut self) {
+        if let Some(sparse) = self.sparse.take() {
+            debug!("Making the table of {} non-zeros dense", sparse.nnz());
+            self.table = sparse.to_dense();
+        }
+    }
+
+    fn negate_row(&mut self, row: usize) {
+        match self.sparse.as_mut() {
+            Some(sparse) => sparse.negate_row(row),
+            None => self
+                .table
+                .row_mut(row)
+                .iter_mut()
+                .for_each(|i| *i = -i.clone()),
+        }
+        for (_, column) in self.fixed_columns.iter_mut() {
+            column[row] = -column[row].clone();
+        }
+    }
+
+    fn negate_column(&mut self, column: usize) {
+        match self.sparse.as_mut() {
+            Some(sparse) => sparse.negate_column(column),
+            None => self
+                .table
+                .column_mut(column)
+                .iter_mut()
+                .for_each(|i| *i = -i.clone()),
+        }
+    }
+
+    fn toggle_at_upper(&mut self, name: String) {
+        if let Some(i) = self.at_upper.iter().position(|i| *i == name) {
+            self.at_upper.remove(i);
+        } else {
+            self.at_upper.push(name);
+        }
+    }
+
+    // basic variable above its upper bound is complemented, so only a negative free coeff is left
+    fn complement_above_upper(&mut self, row: usize) {
+        let value = self.table[[row, self.table.ncols() - 1]].clone();
+        if let Some(upper) = self.upper.get(&self.supp_var[row]) {
+            if value > upper.clone() + self.tolerance.feasibility.clone() {
+                debug!("{} is above its upper bound", self.supp_var[row]);
+                self.complement_row(row);
+            }
+        }
+    }
+
+    fn rename(&mut self, old: &str, new: &str) {
+        for name in self
+            .base_var
+            .iter_mut()
+            .chain(self.supp_var.iter_mut())
+            .chain(self.var_order.iter_mut())
+        {
+            if name == old {
+                *name = new.to_string();
+            }
+        }
+    }
+
+    // pivots the zero slack of every equality out of the basis and drops its column
+    fn eliminate_equalities(&mut self) -> Result<(), SimplexError<T>> {
+        let mut i = 0;
+        while i < self.table.nrows() - 1 {
+            if !self.is_equality(&self.supp_var[i]) {
+                i += 1;
+                continue;
+            }
+            let mut column = None;
+            for j in 0..self.table.ncols() - 1 {
+                let coeff = self.table[[i, j]].abs();
+                if coeff > self.tolerance.pivot
+                    && column.is_none_or(|c: usize| coeff > self.table[[i, c]].abs())
+                {
+                    column = Some(j);
+                }
+            }
+            if let Some(j) = column {
+                debug!(
+                    "Eliminating equality {} on pivot i: {}\tj: {}",
+                    self.supp_var[i], i, j
+                );
+                self.fix_column(i, j)?;
+                i += 1;
+            } else if self.table[[i, self.table.ncols() - 1]].abs() <= self.tolerance.feasibility {
+                debug!("Removing redundant row {}", i);
+                self.remove_row(i);
+            } else {
+                return Err(SimplexError::NoSolutionsError(Some(self.farkas(i))));
+            }
+        }
+        Ok(())
+    }
+
+    // methods building new tasks from the model need a table that wasn't optimised yet
+    fn check_unoptimised(&self) -> Result<(), SimplexError<T>> {
+        if self.iterations != 0 || !self.substitutions.is_empty() {
+            return Err(SimplexError::InvalidDataError);
+        }
+        Ok(())
+    }
+
+    // pivots the zero slack of the row out of the basis and keeps its column apart
+    fn fix_column(&mut self, row: usize, column: usize) -> Result<(), SimplexError<T>> {
+        self.pivot((row, column))?;
+        let values = self.table.column(column).to_owned();
+        self.fixed_columns
+            .push((self.base_var[column].clone(), values));
+        self.table.remove_index(Axis(1), column);
+        self.base_var.remove(column);
+        Ok(())
+    }
+
+    fn remove_row(&mut self, row: usize) {
+        self.table.remove_index(Axis(0), row);
+        self.supp_var.remove(row);
+        for (_, column) in self.fixed_columns.iter_mut() {
+            column.remove_index(Axis(0), row);
+        }
+    }
+
+    // adds a row above the function row, the fixed columns get zero in it
+    fn insert_row(&mut self, name: String, row: Array1<T>) {
+        let last = self.table.nrows() - 1;
+        let mut table = Array2::<T>::zeros((last + 2, self.table.ncols()));
+        table
+            .slice_mut(s![..last, ..])
+            .assign(&self.table.slice(s![..last, ..]));
+        table.row_mut(last).assign(&row);
+        table.row_mut(last + 1).assign(&self.table.row(last));
+        self.table = table;
+        self.supp_var.insert(last, name.clone());
+        self.var_order.push(name);
+        for (_, column) in self.fixed_columns.iter_mut() {
+            let mut extended = column.to_vec();
+            extended.insert(last, T::zero());
+            *column = Array1::from_vec(extended);
+        }
+    }
+
+    fn is_equality(&self, name: &String) -> bool {
+        self.constraints
+            .iter()
+            .position(|i| i == name)
+            .is_some_and(|i| self.senses[i] == Sense::Equal)
+    }
+
+    // pivots artificial variables left at zero level out of the basis
+    // and removes their columns together with the penalty row
+    fn drop_artificial(&mut self) -> Result<(), SimplexError<T>> {
+        let mut i = 0;
+        while i < self.table.nrows() - 1 {
+            if !self.artificial.contains(&self.supp_var[i]) {
+                i += 1;
+                continue;
+            }
+            let column = (0..self.table.ncols() - 1).find(|&j| {
+                !self.artificial.contains(&self.base_var[j])
+                    && self.table[[i, j]].abs() > self.tolerance.pivot
+            });
+            if let Some(j) = column {
+                debug!(
+                    "Removing artificial {} on pivot i: {}\tj: {}",
+                    self.supp_var[i], i, j
+                );
+                self.pivot((i, j))?;
+                i += 1;
+            } else {
+                // the row is a linear combination of the others
+                debug!("Removing redundant row {}", i);
+                self.remove_row(i);
+            }
+        }
+        for j in (0..self.table.ncols() - 1).rev() {
+            if self.artificial.contains(&self.base_var[j]) {
+                self.table.remove_index(Axis(1), j);
+                self.base_var.remove(j);
+            }
+        }
+        self.penalty = None;
+        self.artificial.clear();
+        Ok(())
+    }
+
+    fn find_penalty_column(&self) -> Option<usize> {
+        let penalty = self.penalty.as_ref()?;
+        self.choose_column(penalty.view(), |j| penalty[j] > self.tolerance.optimality)
+    }
+
+    // M dominates any finite coeff, so the function row only breaks ties
+    fn find_big_m_column(&self) -> Result<Option<usize>, SimplexError<T>> {
+        let Some(penalty) = self.penalty.as_ref() else {
+            return Ok(None);
         };
-        let mut has_positive = false;
-        for i in self.table.column(j) {
-            if i.is_sign_positive() {
-                has_positive = true;
-                break;
+        let last = self.table.nrows() - 1;
+        let optimality = self.tolerance.optimality.clone();
+        if let Some(j) = self.choose_column(penalty.view(), |j| penalty[j] > optimality) {
+            return Ok(Some(j));
+        }
+        // the artificial sum can't decrease any more, a positive one means there are no solutions
+        self.check_artificial_sum()?;
+        Ok(self.choose_column(self.table.row(last), |j| {
+            penalty[j].abs() <= optimality && self.table[[last, j]] > optimality
+        }))
+    }
+
+    // picks the entering column among the eligible ones according to the pivot rule,
+    // costs is the function row the columns are priced by
+    fn choose_column<F: Fn(usize) -> bool>(
+        &self,
+        costs: ArrayView1<T>,
+        eligible: F,
+    ) -> Option<usize> {
+        let candidates = (0..self.table.ncols() - 1).filter(|&j| eligible(j));
+        if self.pivot_rule == PivotRule::Bland {
+            return candidates.min_by_key(|&j| self.var_index(&self.base_var[j]));
+        }
+        let mut best = None;
+        let mut best_index = None;
+        for j in candidates {
+            // scores only rank the columns, so they are compared as floats
+            let score = match self.pivot_rule {
+                PivotRule::Dantzig => to_f64(costs[j].clone()),
+                PivotRule::GreatestImprovement => match self.find_pivot_row(j) {
+                    Some(leaving) => to_f64(costs[j].clone() * self.step_length(j, leaving)),
+                    None => f64::INFINITY,
+                },
+                PivotRule::SteepestEdge => {
+                    let norm = self
+                        .table
+                        .column(j)
+                        .iter()
+                        .take(self.table.nrows() - 1)
+                        .fold(1f64, |acc, a| acc + to_f64(a.clone() * a.clone()));
+                    to_f64(costs[j].clone()) / norm.sqrt()
+                }
+                PivotRule::Bland => unreachable!(),
+            };
+            if best.is_none() || score > best.unwrap() {
+                best = Some(score);
+                best_index = Some(j);
             }
         }
-        if !has_positive {
-            return Err(SimplexError::UnlimitedError);
+        best_index
+    }
+
+    // complemented variables are marked in the printed table
+    fn label(&self, name: &String) -> String {
+        if self.at_upper.contains(name) {
+            format!("~{}", name)
+        } else {
+            name.clone()
         }
-        if let Some(i) = self.find_pivot_row(j) {
-            return Ok((i, j));
+    }
+
+    // first of prefix1, prefix2, ... that no variable is named, keeps clear of the user's names
+    fn fresh_name(&self, prefix: &str) -> String {
+        (1..)
+            .map(|k| format!("{}{}", prefix, k))
+            .find(|name| !self.is_taken(name))
+            .unwrap()
+    }
+
+    // name of a column standing for a part of the decision variable, name + suffix if it's free
+    fn split_name(&self, name: &str, suffix: &str) -> String {
+        let split = format!("{}{}", name, suffix);
+        if self.is_taken(&split) {
+            self.fresh_name(&split)
+        } else {
+            split
+        }
+    }
+
+    fn is_taken(&self, name: &String) -> bool {
+        self.var_order.contains(name)
+            || self.variables.contains(name)
+            || self.constraints.contains(name)
+    }
+
+    fn var_index(&self, name: &str) -> usize {
+        self.var_order
+            .iter()
+            .position(|i| i == name)
+            .unwrap_or(usize::MAX)
+    }
+
+    fn insert_column(&mut self, name: String, column: Array1<T>) {
+        let last = self.shape().1 - 1;
+        if let Some(sparse) = self.sparse.as_mut() {
+            sparse.insert_column(last, column.iter().cloned().enumerate().collect());
+        } else {
+            self.insert_dense_column(last, column);
+        }
+        self.base_var.insert(last, name);
+        if let Some(penalty) = self.penalty.as_mut() {
+            let mut extended = penalty.to_vec();
+            extended.insert(last, T::zero());
+            *penalty = Array1::from_vec(extended);
+        }
+    }
+
+    fn insert_dense_column(&mut self, last: usize, column: Array1<T>) {
+        let mut table = Array2::<T>::zeros((self.table.nrows(), last + 2));
+        table
+            .slice_mut(s![.., ..last])
+            .assign(&self.table.slice(s![.., ..last]));
+        table.column_mut(last).assign(&column);
+        table.column_mut(last + 1).assign(&self.table.column(last));
+        self.table = table;
+    }
+
+    fn iterate(&mut self) -> Result<(), SimplexError<T>> {
+        let (leaving, j) = self.find_pivot()?;
+        debug!("Current pivot: {:?}\tj:{}", leaving, j);
+        self.step(j, leaving)
+    }
+
+    fn step(&mut self, column: usize, leaving: Leaving) -> Result<(), SimplexError<T>> {
+        match leaving {
+            Leaving::Row(i) => self.pivot((i, column)),
+            Leaving::UpperRow(i) => {
+                self.complement_row(i);
+                self.pivot((i, column))
+            }
+            Leaving::Flip => {
+                self.check_iteration_limit()?;
+                debug!("Flipping {} to its bound", self.base_var[column]);
+                self.complement_column(column);
+                self.iterations += 1;
+                Ok(())
+            }
+        }
+    }
+
+    // how far the entering variable moves before it is stopped
+    fn step_length(&self, column: usize, leaving: Leaving) -> T {
+        let last = self.table.ncols() - 1;
+        match leaving {
+            Leaving::Row(i) => self.table[[i, last]].clone() / self.table[[i, column]].clone(),
+            Leaving::UpperRow(i) => {
+                (self.upper[&self.supp_var[i]].clone() - self.table[[i, last]].clone())
+                    / -self.table[[i, column]].clone()
+            }
+            Leaving::Flip => self.upper[&self.base_var[column]].clone(),
+        }
+    }
+
+    fn pivot(&mut self, pivot: (usize, usize)) -> Result<(), SimplexError<T>> {
+        self.check_iteration_limit()?;
+        self.transform(pivot);
+        std::mem::swap(&mut self.base_var[pivot.1], &mut self.supp_var[pivot.0]);
+        self.iterations += 1;
+        let entering = self.supp_var[pivot.0].clone();
+        let leaving = self.base_var[pivot.1].clone();
+        self.record_basis(self.basis_hash(), entering, leaving)
+    }
+
+    // every pivot and bound flip counts, whatever phase it is made in
+    fn check_iteration_limit(&self) -> Result<(), SimplexError<T>> {
+        if let Some(max) = self.max_iterations {
+            if self.iterations >= max {
+                debug!("Iteration limit {} reached", max);
+                return Err(SimplexError::IterationLimitError(self.iterations));
+            }
+        }
+        Ok(())
+    }
+
+    // remembers the basis reached by the pivot, a repeated one means the pivots cycle
+    fn record_basis(
+        &mut self,
+        hash: u64,
+        entering: String,
+        leaving: String,
+    ) -> Result<(), SimplexError<T>> {
+        self.pivots.push((entering, leaving));
+        if let Some(&start) = self.history.get(&hash) {
+            debug!("Basis repeated after {} pivots", self.pivots.len() - start);
+            return Err(SimplexError::CyclingError(
+                self.iterations,
+                self.pivots[start..].to_vec(),
+            ));
+        }
+        self.history.insert(hash, self.pivots.len());
+        Ok(())
+    }
+
+    fn reset_history(&mut self, hash: u64) {
+        self.pivots.clear();
+        self.history.clear();
+        self.history.insert(hash, 0);
+    }
+
+    fn basis_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.supp_var.hash(&mut hasher);
+        let mut at_upper = self.at_upper.clone();
+        at_upper.sort_unstable();
+        at_upper.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn find_pivot(&self) -> Result<(Leaving, usize), SimplexError<T>> {
+        let last = self.table.nrows() - 1;
+        let j = self
+            .choose_column(self.table.row(last), |j| {
+                self.table[[last, j]] > self.tolerance.optimality
+            })
+            .ok_or(SimplexError::UnableToCalculateError)?;
+        if let Some(leaving) = self.find_pivot_row(j) {
+            Ok((leaving, j))
         } else {
-            return Err(SimplexError::UnlimitedError);
+            Err(SimplexError::UnlimitedError(Some(Box::new(self.ray(j)))))
         }
     }
 
-    fn find_pivot_row(&self, column: usize) -> Option<usize> {
+    fn find_dual_pivot_column(&self, row: usize) -> Option<usize> {
+        let last = self.table.nrows() - 1;
         let mut min = None;
         let mut min_index = None;
+        for j in 0..self.table.ncols() - 1 {
+            if self.table[[row, j]] >= -self.tolerance.pivot.clone() {
+                continue;
+            }
+            let relation = self.table[[last, j]].clone() / self.table[[row, j]].clone();
+            if min.as_ref().is_none_or(|min| relation < *min) {
+                min = Some(relation);
+                min_index = Some(j);
+            }
+        }
+        min_index
+    }
+
+    // ratio test, bounded variables may also stop at their upper bounds
+    fn find_pivot_row(&self, column: usize) -> Option<Leaving> {
+        let mut min = None;
+        let mut min_index: Option<usize> = None;
+        let mut leaving = None;
         for i in self.table.column(self.table.ncols() - 1).iter().enumerate() {
             // don't check the function coeffs
             if i.0 >= self.table.nrows() - 1 {
                 break;
             }
-            let relation = i.1 / self.table[[i.0, column]];
-            if (min.is_none() || relation < min.unwrap()) && relation.is_sign_positive() && relation.is_finite() {
+            let coeff = self.table[[i.0, column]].clone();
+            // round-off around zero counts as a degenerate row
+            let value = if i.1.abs() <= self.tolerance.feasibility {
+                T::zero()
+            } else {
+                i.1.clone()
+            };
+            let upper = self.upper.get(&self.supp_var[i.0]);
+            // only rows where the free coeff and the pivot have the same sign
+            let (relation, row) = if (coeff > self.tolerance.pivot && value >= T::zero())
+                || (coeff < -self.tolerance.pivot.clone() && value < T::zero())
+            {
+                (value / coeff, Leaving::Row(i.0))
+            } else if coeff < -self.tolerance.pivot.clone() && value >= T::zero() && upper.is_some()
+            {
+                (
+                    (upper.unwrap().clone() - value) / -coeff,
+                    Leaving::UpperRow(i.0),
+                )
+            } else {
+                continue;
+            };
+            // Bland's rule breaks ties by the smallest index of the leaving variable
+            let tie_break = self.pivot_rule == PivotRule::Bland
+                && min.as_ref().is_some_and(|min: &T| {
+                    (relation.clone() - min.clone()).abs() <= self.tolerance.feasibility
+                })
+                && self.var_index(&self.supp_var[i.0])
+                    < self.var_index(&self.supp_var[min_index.unwrap()]);
+            if min.as_ref().is_none_or(|min| relation < *min) || tie_break {
                 min = Some(relation);
                 min_index = Some(i.0);
+                leaving = Some(row);
             }
         }
-        min_index
+        // a bound flip keeps the basis, so it wins the ties
+        if let Some(upper) = self.upper.get(&self.base_var[column]) {
+            if min.is_none_or(|min| *upper <= min) {
+                return Some(Leaving::Flip);
+            }
+        }
+        leaving
     }
 
     fn transform(&mut self, pivot: (usize, usize)) {
-        let pivot_cpy = self.table[[pivot.0, pivot.1]];
+        let pivot_cpy = self.table[[pivot.0, pivot.1]].clone();
+        for (_, column) in self.fixed_columns.iter_mut() {
+            let factor = column[pivot.0].clone() / pivot_cpy.clone();
+            for i in 0..self.table.nrows() {
+                if i != pivot.0 {
+                    column[i] -= self.table[[i, pivot.1]].clone() * factor.clone();
+                }
+            }
+            column[pivot.0] = factor;
+        }
+        if let Some(penalty) = self.penalty.as_mut() {
+            let factor = penalty[pivot.1].clone() / pivot_cpy.clone();
+            for j in 0..self.table.ncols() {
+                if j != pivot.1 {
+                    penalty[j] -= self.table[[pivot.0, j]].clone() * factor.clone();
+                }
+            }
+            penalty[pivot.1] = -factor;
+        }
         for i in 0..self.table.nrows() {
             for j in 0..self.table.ncols() {
                 if i == pivot.0 || j == pivot.1 {
                     continue;
                 }
-                self.table[[i, j]] -=
-                    self.table[[pivot.0, j]] * self.table[[i, pivot.1]] / pivot_cpy;
+                let delta = self.table[[pivot.0, j]].clone() * self.table[[i, pivot.1]].clone()
+                    / pivot_cpy.clone();
+                self.table[[i, j]] -= delta;
             }
         }
         for i in self.table.row_mut(pivot.0) {
-            *i /= pivot_cpy;
+            *i /= pivot_cpy.clone();
         }
         for i in self.table.column_mut(pivot.1) {
-            *i /= -pivot_cpy;
+            *i /= -pivot_cpy.clone();
         }
-        self.table[[pivot.0, pivot.1]] = 1f64 / pivot_cpy;
+        self.table[[pivot.0, pivot.1]] = T::one() / pivot_cpy;
     }
+
+    // a dense copy of a sparse table may not fit in memory, so only the non-zeros
+    // of every row are printed with the labels of their columns
+    fn fmt_sparse(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        sparse: &SparseMatrix<T>,
+    ) -> std::fmt::Result {
+        let mut rows = vec![Vec::new(); sparse.nrows()];
+        for j in 0..sparse.ncols() {
+            for (i, value) in sparse.column(j) {
+                rows[i].push((j, value));
+            }
+        }
+        for (i, row) in rows.iter().enumerate() {
+            write!(f, "{}", self.label(&self.supp_var[i]))?;
+            for (j, value) in row.iter() {
+                write!(f, "\t| {}: ", self.label(&self.base_var[*j]))?;
+                value.fmt_entry(f)?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f)?; // empty line
+        self.print_function(f)?;
+        Ok(())
+    }
+
     pub fn print_function(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for i in self.table.column(self.table.ncols() - 1).iter().enumerate() {
-            if i.0 >= self.table.nrows() - 1 {
-                break;
+        let (nrows, ncols) = self.shape();
+        for i in 0..nrows - 1 {
+            let value = self.free_coeff(i);
+            // default names are plain indices
+            if self.supp_var[i].parse::<usize>().is_ok() {
+                writeln!(f, "X_{} = {}", self.supp_var[i], value)?;
+            } else {
+                writeln!(f, "{} = {}", self.label(&self.supp_var[i]), value)?;
             }
-            writeln!(f, "X_{} = {}", self.supp_var[i.0], i.1)?;
         }
-        write!(f, "F = {}", self.table.column(self.table.ncols() - 1).last().unwrap())?;
+        let value = self.free_coeff(nrows - 1);
+        match (&self.penalty, self.feasibility) {
+            (Some(penalty), Feasibility::BigM) => {
+                write!(f, "F = ")?;
+                fmt_big_m(f, value, penalty[ncols - 1].clone())?;
+            }
+            _ => write!(f, "F = {}", value)?,
+        }
         Ok(())
     }
 }
+
+fn fmt_big_m<T: Scalar>(f: &mut std::fmt::Formatter<'_>, value: T, m: T) -> std::fmt::Result {
+    if m.is_zero() {
+        return value.fmt_entry(f);
+    }
+    if !value.is_zero() {
+        value.fmt_entry(f)?;
+        write!(f, "{}", if m.is_negative() { "-" } else { "+" })?;
+        m.abs().fmt_entry(f)?;
+    } else {
+        m.fmt_entry(f)?;
+    }
+    write!(f, "M")
+}
+
+fn to_f64<T: Scalar>(value: T) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
@@ -0,0 +1,511 @@
+use crate::{Scalar, Sense, SimplexError, Solution, SparseMatrix, Table};
+use log::debug;
+use ndarray::{Array1, Array2};
+
+// one step of presolve, in the order they were made
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reduction<T = f64> {
+    // constraint without coeffs that holds for any point
+    EmptyRow(String),
+    // constraint with a single coeff turned into a bound of the variable
+    SingletonRow(String, String),
+    // constraint removed as a multiple of the other one, which got the tighter side of both
+    DuplicateRow(String, String),
+    // variable with equal bounds, removed at that value
+    FixedColumn(String, T),
+    // variable without coeffs, removed at its best bound
+    EmptyColumn(String, T),
+    // variable that can go to one of its bounds without making anything worse
+    DominatedColumn(String, T),
+}
+
+// maps solutions of the presolved table back to the original task
+#[derive(Debug, Clone)]
+pub struct Postsolve<T = f64> {
+    pub reductions: Vec<Reduction<T>>,
+    constr_coeff: Array2<T>,
+    constr_val: Array1<T>,
+    func_coeff: Array1<T>,
+    senses: Vec<Sense>,
+    upper_bounds: Vec<Option<T>>,
+    variables: Vec<String>,
+    constraints: Vec<String>,
+    tolerance: T,
+}
+
+// task in the working form of presolve, removed rows and columns are only marked
+struct Model<T> {
+    constr_coeff: Array2<T>,
+    constr_val: Vec<T>,
+    // coeffs of the maximised function
+    gain: Vec<T>,
+    senses: Vec<Sense>,
+    lower_bounds: Vec<Option<T>>,
+    upper_bounds: Vec<Option<T>>,
+    rows: Vec<bool>,
+    columns: Vec<bool>,
+}
+
+impl<T: Scalar> Table<T> {
+    // removes the rows and columns the solution doesn't depend on
+    pub fn presolve(&self) -> Result<(Table<T>, Postsolve<T>), SimplexError<T>> {
+        self.check_unoptimised()?;
+        if self.lower_bounds.len() != self.variables.len()
+            || self.upper_bounds.len() != self.variables.len()
+        {
+            return Err(SimplexError::InvalidDataError);
+        }
+        // the reductions take the bounds as they are, crossed ones would fix a variable outside them
+        let crossed = self
+            .lower_bounds
+            .iter()
+            .zip(self.upper_bounds.iter())
+            .any(|bounds| match bounds {
+                (Some(lower), Some(upper)) => {
+                    *lower > upper.clone() + self.tolerance.feasibility.clone()
+                }
+                _ => false,
+            });
+        if crossed {
+            return Err(SimplexError::NoSolutionsError(None));
+        }
+        let (nrows, ncols) = (self.constraints.len(), self.variables.len());
+        // >= rows are negated in the table and so is the function of a minimisation task
+        let row_sign = |i: usize| {
+            if self.senses[i] == Sense::GreaterEqual {
+                -T::one()
+            } else {
+                T::one()
+            }
+        };
+        let constr_coeff =
+            Array2::from_shape_fn((nrows, ncols), |(i, j)| row_sign(i) * self.entry(i, j));
+        let constr_val = Array1::from_shape_fn(nrows, |i| row_sign(i) * self.free_coeff(i));
+        let func_coeff = Array1::from_shape_fn(ncols, |j| {
+            let coeff = self.entry(nrows, j);
+            if self.minimisation_task {
+                -coeff
+            } else {
+                coeff
+            }
+        });
+        let mut model = Model {
+            constr_coeff: constr_coeff.clone(),
+            constr_val: constr_val.to_vec(),
+            gain: (0..ncols).map(|j| self.entry(nrows, j)).collect(),
+            senses: self.senses.clone(),
+            lower_bounds: self.lower_bounds.clone(),
+            upper_bounds: self.upper_bounds.clone(),
+            rows: vec![true; nrows],
+            columns: vec![true; ncols],
+        };
+        let mut reductions = Vec::new();
+        loop {
+            let made = reductions.len();
+            self.remove_empty_rows(&mut model, &mut reductions)?;
+            self.remove_singleton_rows(&mut model, &mut reductions)?;
+            self.remove_duplicate_rows(&mut model, &mut reductions)?;
+            self.remove_fixed_columns(&mut model, &mut reductions);
+            self.remove_dominated_columns(&mut model, &mut reductions);
+            if reductions.len() == made {
+                break;
+            }
+        }
+        let table = self.presolved_table(&model)?;
+        debug!(
+            "Presolve kept {} of {} rows and {} of {} columns",
+            table.constraints.len(),
+            nrows,
+            table.variables.len(),
+            ncols
+        );
+        let postsolve = Postsolve {
+            reductions,
+            constr_coeff,
+            constr_val,
+            func_coeff,
+            senses: self.senses.clone(),
+            upper_bounds: self.upper_bounds.clone(),
+            variables: self.variables.clone(),
+            constraints: self.constraints.clone(),
+            tolerance: self.tolerance.feasibility.clone(),
+        };
+        Ok((table, postsolve))
+    }
+
+    fn remove_empty_rows(
+        &self,
+        model: &mut Model<T>,
+        reductions: &mut Vec<Reduction<T>>,
+    ) -> Result<(), SimplexError<T>> {
+        for i in 0..model.rows.len() {
+            if !model.rows[i]
+                || !model
+                    .row_entries(i, self.tolerance.pivot.clone())
+                    .is_empty()
+            {
+                continue;
+            }
+            let value = model.constr_val[i].clone();
+            let holds = match model.senses[i] {
+                Sense::LessEqual => value >= -self.tolerance.feasibility.clone(),
+                Sense::GreaterEqual => value <= self.tolerance.feasibility,
+                Sense::Equal => value.abs() <= self.tolerance.feasibility,
+            };
+            if !holds {
+                return Err(SimplexError::NoSolutionsError(None));
+            }
+            model.rows[i] = false;
+            push(reductions, Reduction::EmptyRow(self.constraints[i].clone()));
+        }
+        Ok(())
+    }
+
+    fn remove_singleton_rows(
+        &self,
+        model: &mut Model<T>,
+        reductions: &mut Vec<Reduction<T>>,
+    ) -> Result<(), SimplexError<T>> {
+        for i in 0..model.rows.len() {
+            if !model.rows[i] {
+                continue;
+            }
+            let (j, coeff) = match &model.row_entries(i, self.tolerance.pivot.clone())[..] {
+                [entry] => entry.clone(),
+                _ => continue,
+            };
+            let bound = model.constr_val[i].clone() / coeff.clone();
+            // dividing by a negative coeff turns the sense around
+            let (upper, lower) = match model.senses[i] {
+                Sense::LessEqual => (coeff.is_positive(), coeff.is_negative()),
+                Sense::GreaterEqual => (coeff.is_negative(), coeff.is_positive()),
+                Sense::Equal => (true, true),
+            };
+            if upper && model.upper_bounds[j].as_ref().is_none_or(|i| bound < *i) {
+                model.upper_bounds[j] = Some(bound.clone());
+            }
+            if lower && model.lower_bounds[j].as_ref().is_none_or(|i| bound > *i) {
+                model.lower_bounds[j] = Some(bound);
+            }
+            if let (Some(lower), Some(upper)) =
+                (model.lower_bounds[j].clone(), model.upper_bounds[j].clone())
+            {
+                if lower > upper + self.tolerance.feasibility.clone() {
+                    return Err(SimplexError::NoSolutionsError(None));
+                }
+            }
+            model.rows[i] = false;
+            push(
+                reductions,
+                Reduction::SingletonRow(self.constraints[i].clone(), self.variables[j].clone()),
+            );
+        }
+        Ok(())
+    }
+
+    fn remove_duplicate_rows(
+        &self,
+        model: &mut Model<T>,
+        reductions: &mut Vec<Reduction<T>>,
+    ) -> Result<(), SimplexError<T>> {
+        let entries: Vec<Vec<(usize, T)>> = (0..model.rows.len())
+            .map(|i| model.row_entries(i, self.tolerance.pivot.clone()))
+            .collect();
+        for i in 0..model.rows.len() {
+            for k in i + 1..model.rows.len() {
+                if !model.rows[i] || !model.rows[k] || entries[i].len() != entries[k].len() {
+                    continue;
+                }
+                let ratio = match (entries[i].first(), entries[k].first()) {
+                    (Some((j, a)), Some((l, b))) if j == l => b.clone() / a.clone(),
+                    _ => continue,
+                };
+                let proportional = entries[i].iter().zip(entries[k].iter()).all(|(a, b)| {
+                    a.0 == b.0
+                        && (a.1.clone() * ratio.clone() - b.1.clone()).abs() <= self.tolerance.pivot
+                });
+                if !proportional {
+                    continue;
+                }
+                // both rows limit the same sum, written in the scale of row i
+                let mut range = model.range(i, T::one());
+                let other = model.range(k, ratio);
+                if other
+                    .0
+                    .as_ref()
+                    .is_some_and(|k| range.0.as_ref().is_none_or(|i| k > i))
+                {
+                    range.0 = other.0;
+                }
+                if other
+                    .1
+                    .as_ref()
+                    .is_some_and(|k| range.1.as_ref().is_none_or(|i| k < i))
+                {
+                    range.1 = other.1;
+                }
+                let (sense, value) = match range {
+                    (Some(lower), Some(upper))
+                        if lower > upper.clone() + self.tolerance.feasibility.clone() =>
+                    {
+                        return Err(SimplexError::NoSolutionsError(None));
+                    }
+                    (Some(lower), Some(upper))
+                        if upper.clone() - lower.clone() <= self.tolerance.feasibility =>
+                    {
+                        (Sense::Equal, upper)
+                    }
+                    // a range needs both rows
+                    (Some(_), Some(_)) => continue,
+                    (Some(lower), None) => (Sense::GreaterEqual, lower),
+                    (None, Some(upper)) => (Sense::LessEqual, upper),
+                    (None, None) => continue,
+                };
+                model.senses[i] = sense;
+                model.constr_val[i] = value;
+                model.rows[k] = false;
+                push(
+                    reductions,
+                    Reduction::DuplicateRow(
+                        self.constraints[k].clone(),
+                        self.constraints[i].clone(),
+                    ),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_fixed_columns(&self, model: &mut Model<T>, reductions: &mut Vec<Reduction<T>>) {
+        for j in 0..model.columns.len() {
+            if !model.columns[j] {
+                continue;
+            }
+            if let (Some(lower), Some(upper)) =
+                (model.lower_bounds[j].clone(), model.upper_bounds[j].clone())
+            {
+                if (upper - lower.clone()).abs() <= self.tolerance.feasibility {
+                    model.fix(j, lower.clone());
+                    push(
+                        reductions,
+                        Reduction::FixedColumn(self.variables[j].clone(), lower),
+                    );
+                }
+            }
+        }
+    }
+
+    // a column whose every coeff makes its rows easier to hold as the variable goes one way,
+    // while the function doesn't get worse, can be left at the bound on that side
+    fn remove_dominated_columns(&self, model: &mut Model<T>, reductions: &mut Vec<Reduction<T>>) {
+        for j in 0..model.columns.len() {
+            if !model.columns[j] {
+                continue;
+            }
+            let column: Vec<(usize, T)> = (0..model.rows.len())
+                .filter(|&i| model.rows[i])
+                .map(|i| (i, model.constr_coeff[[i, j]].clone()))
+                .filter(|(_, a)| a.abs() > self.tolerance.pivot)
+                .collect();
+            let eases = |up: bool| {
+                column.iter().all(|(i, a)| match model.senses[*i] {
+                    Sense::LessEqual => a.is_negative() == up,
+                    Sense::GreaterEqual => a.is_positive() == up,
+                    Sense::Equal => false,
+                })
+            };
+            let gain = model.gain[j].clone();
+            let down = (gain <= self.tolerance.optimality && eases(false))
+                .then_some(model.lower_bounds[j].clone())
+                .flatten();
+            let up = (gain >= -self.tolerance.optimality.clone() && eases(true))
+                .then_some(model.upper_bounds[j].clone())
+                .flatten();
+            // without the bound the solver has to decide
+            let value = match down.or(up) {
+                Some(value) => value,
+                None if column.is_empty() && gain.abs() <= self.tolerance.optimality => {
+                    match (model.lower_bounds[j].clone(), model.upper_bounds[j].clone()) {
+                        (Some(lower), _) => lower,
+                        (None, Some(upper)) => upper,
+                        (None, None) => T::zero(),
+                    }
+                }
+                None => continue,
+            };
+            model.fix(j, value.clone());
+            let name = self.variables[j].clone();
+            push(
+                reductions,
+                if column.is_empty() {
+                    Reduction::EmptyColumn(name, value)
+                } else {
+                    Reduction::DominatedColumn(name, value)
+                },
+            );
+        }
+    }
+
+    fn presolved_table(&self, model: &Model<T>) -> Result<Table<T>, SimplexError<T>> {
+        let rows: Vec<usize> = (0..model.rows.len()).filter(|&i| model.rows[i]).collect();
+        let columns: Vec<usize> = (0..model.columns.len())
+            .filter(|&j| model.columns[j])
+            .collect();
+        let constr_coeff = Array2::from_shape_fn((rows.len(), columns.len()), |(i, j)| {
+            model.constr_coeff[[rows[i], columns[j]]].clone()
+        });
+        let constr_val = Array1::from_shape_fn(rows.len(), |i| model.constr_val[rows[i]].clone());
+        let func_coeff = Array1::from_shape_fn(columns.len(), |j| {
+            let gain = model.gain[columns[j]].clone();
+            if self.minimisation_task {
+                -gain
+            } else {
+                gain
+            }
+        });
+        let mut table = match self.sparse {
+            Some(_) => Table::from_sparse(
+                SparseMatrix::from_dense(&constr_coeff),
+                constr_val,
+                func_coeff,
+                self.minimisation_task,
+            )?,
+            None => Table::new(constr_coeff, constr_val, func_coeff, self.minimisation_task)?,
+        };
+        let variables: Vec<&str> = columns
+            .iter()
+            .map(|&j| self.variables[j].as_str())
+            .collect();
+        let constraints: Vec<&str> = rows.iter().map(|&i| self.constraints[i].as_str()).collect();
+        table.set_names(&variables, &constraints)?;
+        table.set_senses(&rows.iter().map(|&i| model.senses[i]).collect::<Vec<_>>())?;
+        table.lower_bounds = columns
+            .iter()
+            .map(|&j| model.lower_bounds[j].clone())
+            .collect();
+        table.upper_bounds = columns
+            .iter()
+            .map(|&j| model.upper_bounds[j].clone())
+            .collect();
+        table.feasibility = self.feasibility;
+        table.algorithm = self.algorithm;
+        table.scaling = self.scaling;
+        table.integrality = columns.iter().map(|&j| self.integrality[j]).collect();
+        table.node_selection = self.node_selection;
+        table.branching_rule = self.branching_rule;
+        table.max_nodes = self.max_nodes;
+        table.max_cuts = self.max_cuts;
+        table.pivot_rule = self.pivot_rule;
+        table.max_iterations = self.max_iterations;
+        table.tolerance = self.tolerance.clone();
+        Ok(table)
+    }
+}
+
+impl<T: Scalar> Model<T> {
+    fn row_entries(&self, row: usize, tolerance: T) -> Vec<(usize, T)> {
+        (0..self.columns.len())
+            .filter(|&j| self.columns[j])
+            .map(|j| (j, self.constr_coeff[[row, j]].clone()))
+            .filter(|(_, a)| a.abs() > tolerance)
+            .collect()
+    }
+
+    // (lowest, highest) value of the row sum divided by the ratio
+    fn range(&self, row: usize, ratio: T) -> (Option<T>, Option<T>) {
+        let value = self.constr_val[row].clone() / ratio.clone();
+        let (lower, upper) = match self.senses[row] {
+            Sense::LessEqual => (None, Some(value)),
+            Sense::GreaterEqual => (Some(value), None),
+            Sense::Equal => (Some(value.clone()), Some(value)),
+        };
+        if ratio.is_negative() {
+            (upper, lower)
+        } else {
+            (lower, upper)
+        }
+    }
+
+    // moves the column to the right-hand side at the value
+    fn fix(&mut self, column: usize, value: T) {
+        for i in 0..self.rows.len() {
+            let coeff = self.constr_coeff[[i, column]].clone();
+            self.constr_val[i] -= coeff * value.clone();
+        }
+        self.columns[column] = false;
+    }
+}
+
+impl<T: Scalar> Postsolve<T> {
+    // solution of the original task from the solution of the presolved table
+    pub fn solution(&self, presolved: &Solution<T>) -> Solution<T> {
+        let mut variables = vec![T::zero(); self.variables.len()];
+        for (name, value) in presolved
+            .variable_names
+            .iter()
+            .zip(presolved.variables.iter())
+        {
+            variables[self.variable_index(name)] = value.clone();
+        }
+        let mut basis = presolved.basis.clone();
+        for reduction in self.reductions.iter().rev() {
+            match reduction {
+                Reduction::FixedColumn(name, value)
+                | Reduction::EmptyColumn(name, value)
+                | Reduction::DominatedColumn(name, value) => {
+                    variables[self.variable_index(name)] = value.clone();
+                }
+                // slacks of the removed rows stay basic
+                Reduction::EmptyRow(name)
+                | Reduction::SingletonRow(name, _)
+                | Reduction::DuplicateRow(name, _) => basis.push(name.clone()),
+            }
+        }
+        let (nrows, ncols) = (self.constraints.len(), self.variables.len());
+        let slacks = (0..nrows)
+            .map(|i| {
+                let slack = self.constr_val[i].clone()
+                    - (0..ncols).fold(T::zero(), |acc, j| {
+                        acc + self.constr_coeff[[i, j]].clone() * variables[j].clone()
+                    });
+                match self.senses[i] {
+                    Sense::LessEqual => slack,
+                    Sense::GreaterEqual => -slack,
+                    Sense::Equal => T::zero(),
+                }
+            })
+            .collect();
+        let at_upper = self
+            .variables
+            .iter()
+            .zip(variables.iter().zip(self.upper_bounds.iter()))
+            .filter(|(_, (value, upper))| {
+                upper
+                    .as_ref()
+                    .is_some_and(|upper| ((*value).clone() - upper.clone()).abs() <= self.tolerance)
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        Solution {
+            objective: (0..ncols).fold(T::zero(), |acc, j| {
+                acc + self.func_coeff[j].clone() * variables[j].clone()
+            }),
+            variables,
+            slacks,
+            variable_names: self.variables.clone(),
+            constraint_names: self.constraints.clone(),
+            basis,
+            at_upper,
+            iterations: presolved.iterations,
+        }
+    }
+
+    fn variable_index(&self, name: &String) -> usize {
+        self.variables.iter().position(|i| i == name).unwrap()
+    }
+}
+
+fn push<T: Scalar>(reductions: &mut Vec<Reduction<T>>, reduction: Reduction<T>) {
+    debug!("Presolve: {:?}", reduction);
+    reductions.push(reduction);
+}
@@ -0,0 +1,734 @@
+use crate::{Leaving, PivotRule, Scalar, SimplexError, Solution, SparseMatrix, Table};
+use log::debug;
+use ndarray::Array1;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+// number of product form updates before the basis is factorised again
+const REFACTOR_FREQUENCY: usize = 64;
+
+// a pivot may be this much smaller than the largest entry of its column, anything
+// smaller risks growth of the entries, anything larger fills in more of the factors
+const PIVOT_THRESHOLD: f64 = 0.1;
+
+// columns searched for the pivot of every elimination step
+const MARKOWITZ_SEARCH: usize = 4;
+
+// sparse LU factors of the basis with the product form updates on top of them:
+// B = L U E_1 E_2 ... E_k, every eta matrix E is the identity with one column
+// replaced, so only the nonzeros of that column are kept
+struct Factor<T> {
+    eliminations: Vec<Elimination<T>>,
+    etas: Vec<Eta<T>>,
+}
+
+// one step of gaussian elimination, pivot on the given row and basis position
+struct Elimination<T> {
+    row: usize,
+    column: usize,
+    pivot: T,
+    // the other nonzeros of the pivot row, a row of U
+    upper: Vec<(usize, T)>,
+    // multiples of the pivot row taken from the rows left, a column of L
+    lower: Vec<(usize, T)>,
+}
+
+struct Eta<T> {
+    row: usize,
+    pivot: T,
+    // the other nonzeros of the column
+    column: Vec<(usize, T)>,
+}
+
+impl<T: Scalar> Factor<T> {
+    // eliminates the basis columns with Markowitz ordering, the pivot that would fill
+    // in the fewest entries is taken among the ones large enough for stability
+    fn new(matrix: &SparseMatrix<T>, basis: &[usize]) -> Option<Factor<T>> {
+        let m = basis.len();
+        let mut rows: Vec<Vec<(usize, T)>> = vec![Vec::new(); m];
+        let mut cols: Vec<Vec<usize>> = vec![Vec::new(); m];
+        for (k, &j) in basis.iter().enumerate() {
+            for (i, value) in matrix.column(j) {
+                if !value.is_zero() {
+                    rows[i].push((k, value));
+                    cols[k].push(i);
+                }
+            }
+        }
+        let mut active = vec![true; m];
+        let mut eliminations = Vec::with_capacity(m);
+        // where the columns of the row being updated are, so it is walked only once
+        let mut slot: Vec<Option<usize>> = vec![None; m];
+        for _ in 0..m {
+            let (r, c) = Self::markowitz_pivot(&rows, &cols, &active)?;
+            active[c] = false;
+            let mut pivot_row = std::mem::take(&mut rows[r]);
+            for (j, _) in pivot_row.iter() {
+                cols[*j].retain(|&i| i != r);
+            }
+            let at = pivot_row.iter().position(|(j, _)| *j == c)?;
+            let (_, pivot) = pivot_row.swap_remove(at);
+            let mut lower = Vec::new();
+            for i in std::mem::take(&mut cols[c]) {
+                let row = &mut rows[i];
+                let at = row.iter().position(|(j, _)| *j == c)?;
+                let (_, value) = row.swap_remove(at);
+                let multiplier = value / pivot.clone();
+                for (k, (j, _)) in row.iter().enumerate() {
+                    slot[*j] = Some(k);
+                }
+                for (j, u) in pivot_row.iter() {
+                    let change = multiplier.clone() * u.clone();
+                    match slot[*j] {
+                        Some(k) => row[k].1 -= change,
+                        None => {
+                            row.push((*j, -change));
+                            cols[*j].push(i);
+                        }
+                    }
+                }
+                for (j, _) in row.iter() {
+                    slot[*j] = None;
+                }
+                // entries that cancelled out are dropped from the pattern
+                row.retain(|(j, value)| {
+                    if value.is_zero() {
+                        cols[*j].retain(|&k| k != i);
+                    }
+                    !value.is_zero()
+                });
+                lower.push((i, multiplier));
+            }
+            eliminations.push(Elimination {
+                row: r,
+                column: c,
+                pivot,
+                upper: pivot_row,
+                lower,
+            });
+        }
+        Some(Factor {
+            eliminations,
+            etas: Vec::new(),
+        })
+    }
+
+    // the sparsest columns are searched first, a column of one entry costs nothing
+    fn markowitz_pivot(
+        rows: &[Vec<(usize, T)>],
+        cols: &[Vec<usize>],
+        active: &[bool],
+    ) -> Option<(usize, usize)> {
+        let mut order: Vec<usize> = (0..cols.len()).filter(|&c| active[c]).collect();
+        order.sort_by_key(|&c| cols[c].len());
+        let mut best: Option<(usize, usize, usize)> = None;
+        for &c in order.iter().take(MARKOWITZ_SEARCH) {
+            // a column without entries is a singular basis
+            if cols[c].is_empty() {
+                return None;
+            }
+            let value = |i: usize| {
+                rows[i]
+                    .iter()
+                    .find(|(j, _)| *j == c)
+                    .map(|(_, value)| crate::to_f64(value.abs()))
+                    .unwrap_or(0.0)
+            };
+            let largest = cols[c].iter().map(|&i| value(i)).fold(0.0, f64::max);
+            for &i in cols[c].iter() {
+                if value(i) < PIVOT_THRESHOLD * largest {
+                    continue;
+                }
+                let cost = (rows[i].len() - 1) * (cols[c].len() - 1);
+                if best.is_none_or(|(least, _, _)| cost < least) {
+                    best = Some((cost, i, c));
+                }
+            }
+            if best.is_some_and(|(least, _, _)| least == 0) {
+                break;
+            }
+        }
+        best.map(|(_, i, c)| (i, c))
+    }
+
+    // replaces the basis column in row r by the one with B^-1 a = alpha
+    fn update(&mut self, r: usize, alpha: &[T]) {
+        self.etas.push(Eta::new(r, alpha));
+    }
+
+    // solves B x = a: L, then U, then the etas
+    fn ftran(&self, a: &[T]) -> Vec<T> {
+        let mut v = a.to_vec();
+        for step in self.eliminations.iter() {
+            let pivot = v[step.row].clone();
+            if pivot.is_zero() {
+                continue;
+            }
+            for (i, multiplier) in step.lower.iter() {
+                v[*i] -= multiplier.clone() * pivot.clone();
+            }
+        }
+        let mut x = vec![T::zero(); v.len()];
+        for step in self.eliminations.iter().rev() {
+            let mut value = v[step.row].clone();
+            for (j, u) in step.upper.iter() {
+                value -= u.clone() * x[*j].clone();
+            }
+            x[step.column] = value / step.pivot.clone();
+        }
+        for eta in self.etas.iter() {
+            let pivot = x[eta.row].clone() / eta.pivot.clone();
+            if pivot.is_zero() {
+                continue;
+            }
+            for (i, value) in eta.column.iter() {
+                x[*i] -= value.clone() * pivot.clone();
+            }
+            x[eta.row] = pivot;
+        }
+        x
+    }
+
+    // solves y B = c: the etas backwards, then U, then L
+    fn btran(&self, c: &[T]) -> Vec<T> {
+        let mut w = c.to_vec();
+        for eta in self.etas.iter().rev() {
+            let mut value = w[eta.row].clone();
+            for (i, coeff) in eta.column.iter() {
+                value -= w[*i].clone() * coeff.clone();
+            }
+            w[eta.row] = value / eta.pivot.clone();
+        }
+        let mut y = vec![T::zero(); w.len()];
+        for step in self.eliminations.iter() {
+            let value = w[step.column].clone() / step.pivot.clone();
+            if !value.is_zero() {
+                for (j, u) in step.upper.iter() {
+                    w[*j] -= value.clone() * u.clone();
+                }
+            }
+            y[step.row] = value;
+        }
+        for step in self.eliminations.iter().rev() {
+            let mut value = y[step.row].clone();
+            for (i, multiplier) in step.lower.iter() {
+                value -= multiplier.clone() * y[*i].clone();
+            }
+            y[step.row] = value;
+        }
+        y
+    }
+}
+
+impl<T: Scalar> Eta<T> {
+    fn new(row: usize, alpha: &[T]) -> Eta<T> {
+        let column = alpha
+            .iter()
+            .enumerate()
+            .filter(|&(i, value)| i != row && !value.is_zero())
+            .map(|(i, value)| (i, value.clone()))
+            .collect();
+        Eta {
+            row,
+            pivot: alpha[row].clone(),
+            column,
+        }
+    }
+}
+
+// the model A x = b, 0 <= x <= upper, min c x, kept by the revised method
+struct Revised<T> {
+    matrix: SparseMatrix<T>,
+    b: Vec<T>,
+    upper: Vec<Option<T>>,
+    names: Vec<String>,
+    // index of every variable for Bland's rule
+    index: Vec<usize>,
+    artificial: Vec<bool>,
+    basis: Vec<usize>,
+    // position of every basic variable in the basis
+    position: Vec<Option<usize>>,
+    at_upper: Vec<bool>,
+    x_b: Vec<T>,
+    factor: Factor<T>,
+}
+
+impl<T: Scalar> Revised<T> {
+    fn refactor(&mut self) -> Result<(), SimplexError<T>> {
+        self.factor =
+            Factor::new(&self.matrix, &self.basis).ok_or(SimplexError::UnableToCalculateError)?;
+        self.compute_x_b();
+        Ok(())
+    }
+
+    fn compute_x_b(&mut self) {
+        let mut rhs = self.b.clone();
+        for j in 0..self.matrix.ncols() {
+            if self.at_upper[j] && self.position[j].is_none() {
+                let upper = self.upper[j].clone().unwrap();
+                for (i, value) in self.matrix.column(j) {
+                    rhs[i] -= value * upper.clone();
+                }
+            }
+        }
+        self.x_b = self.factor.ftran(&rhs);
+    }
+
+    fn dense_column(&self, j: usize) -> Vec<T> {
+        let mut column = vec![T::zero(); self.matrix.nrows()];
+        for (i, value) in self.matrix.column(j) {
+            column[i] = value;
+        }
+        column
+    }
+
+    fn value(&self, j: usize) -> T {
+        match self.position[j] {
+            Some(i) => self.x_b[i].clone(),
+            None if self.at_upper[j] => self.upper[j].clone().unwrap(),
+            None => T::zero(),
+        }
+    }
+
+    fn hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.basis.hash(&mut hasher);
+        self.at_upper.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl<T: Scalar> Table<T> {
+    // same as optimise, but keeps a factorised basis instead of transforming the whole table
+    pub(crate) fn revised_optimise(&mut self) -> Result<Solution<T>, SimplexError<T>> {
+        let (mut model, costs, constant) = self.standard_form()?;
+        debug!(
+            "Revised method on {} rows and {} columns",
+            model.matrix.nrows(),
+            model.matrix.ncols()
+        );
+        let phase_one: Vec<T> = model
+            .artificial
+            .iter()
+            .map(|&i| if i { T::one() } else { T::zero() })
+            .collect();
+        if model.artificial.iter().any(|&i| i) {
+            // the sum of artificial variables can't decrease without limit
+            if self.revised_run(&mut model, &phase_one, false)?.is_some() {
+                return Err(SimplexError::UnableToCalculateError);
+            }
+            let sum = (0..model.names.len())
+                .filter(|&j| model.artificial[j])
+                .fold(T::zero(), |acc, j| acc + model.value(j));
+            if sum > self.tolerance.feasibility {
+                // the duals of phase one are the penalty row coeffs of the slacks in the table
+                let c_b: Vec<T> = model.basis.iter().map(|&j| phase_one[j].clone()).collect();
+                let y = model.factor.btran(&c_b);
+                let coeffs = self
+                    .constraints
+                    .iter()
+                    .map(|name| {
+                        self.supp_var
+                            .iter()
+                            .position(|i| i == name)
+                            .map_or(T::zero(), |i| y[i].clone())
+                    })
+                    .collect();
+                let farkas = self.farkas_from(coeffs, -T::one());
+                return Err(SimplexError::ArtificialSumError(sum, farkas));
+            }
+            self.drive_out_artificial(&mut model)?;
+            debug!("Revised phase one finished");
+        }
+        let unlimited = self.revised_run(&mut model, &costs, true)?;
+        self.write_back(&model, &costs, constant)?;
+        if let Some(q) = unlimited {
+            let column = self.base_var.iter().position(|i| *i == model.names[q]);
+            return Err(SimplexError::UnlimitedError(
+                column.map(|j| Box::new(self.ray(j))),
+            ));
+        }
+        debug!("Revised method finished:\n{}", self);
+        Ok(self.solution())
+    }
+
+    // reads A x = b from the current table: every row is T x + y = S with basic y
+    fn standard_form(&mut self) -> Result<(Revised<T>, Vec<T>, T), SimplexError<T>> {
+        // complemented variables are turned back, bounds are kept in the model instead
+        let (nrows, ncols) = self.shape();
+        for i in 0..nrows - 1 {
+            if self.at_upper.contains(&self.supp_var[i]) {
+                self.complement_row(i);
+            }
+        }
+        for j in 0..ncols - 1 {
+            if self.at_upper.contains(&self.base_var[j]) {
+                self.complement_column(j);
+            }
+        }
+        let m = nrows - 1;
+        let n = ncols - 1;
+        let mut matrix = SparseMatrix::new(m);
+        let mut names = Vec::with_capacity(n + m);
+        let mut costs = Vec::with_capacity(n + m);
+        let mut upper = Vec::with_capacity(n + m);
+        for j in 0..n {
+            let mut column = self.table_column(j);
+            // function row holds minus the costs
+            let cost = match column.last() {
+                Some((i, _)) if *i == m => -column.pop().unwrap().1,
+                _ => T::zero(),
+            };
+            matrix.push_column(column);
+            names.push(self.base_var[j].clone());
+            costs.push(cost);
+            upper.push(self.upper.get(&self.base_var[j]).cloned());
+        }
+        let mut b = vec![T::zero(); m + 1];
+        for (i, value) in self.table_column(n) {
+            b[i] = value;
+        }
+        let constant = b.pop().unwrap();
+        let mut basis = Vec::with_capacity(m);
+        let mut at_upper = vec![false; n + m];
+        let mut artificial = vec![false; n + m];
+        let mut extra = Vec::new();
+        for i in 0..m {
+            let name = &self.supp_var[i];
+            matrix.push_column(std::iter::once((i, T::one())));
+            names.push(name.clone());
+            costs.push(T::zero());
+            let bound = if self.is_equality(name) {
+                Some(T::zero())
+            } else {
+                self.upper.get(name).cloned()
+            };
+            upper.push(bound.clone());
+            let value = b[i].clone();
+            let feasible = value >= -self.tolerance.feasibility.clone()
+                && bound.as_ref().is_none_or(|bound| {
+                    value <= bound.clone() + self.tolerance.feasibility.clone()
+                });
+            if feasible {
+                basis.push(n + i);
+                continue;
+            }
+            // the row variable waits at its nearest bound and an artificial one takes the rest
+            let residual = match bound {
+                Some(bound) if value > bound => {
+                    at_upper[n + i] = true;
+                    value - bound
+                }
+                _ => value,
+            };
+            extra.push((i, residual));
+        }
+        for (i, residual) in extra {
+            let sign = if residual.is_negative() {
+                -T::one()
+            } else {
+                T::one()
+            };
+            matrix.push_column(std::iter::once((i, sign)));
+            let name = self.fresh_name("A");
+            self.var_order.push(name.clone());
+            names.push(name);
+            costs.push(T::zero());
+            upper.push(None);
+            at_upper.push(false);
+            artificial.push(true);
+            basis.push(matrix.ncols() - 1);
+        }
+        let order: HashMap<&String, usize> = self
+            .var_order
+            .iter()
+            .enumerate()
+            .map(|(k, name)| (name, k))
+            .collect();
+        let index = names
+            .iter()
+            .map(|name| order.get(name).copied().unwrap_or(usize::MAX))
+            .collect();
+        let mut position = vec![None; matrix.ncols()];
+        for (i, &j) in basis.iter().enumerate() {
+            position[j] = Some(i);
+        }
+        let mut model = Revised {
+            matrix,
+            b,
+            upper,
+            names,
+            index,
+            artificial,
+            basis,
+            position,
+            at_upper,
+            x_b: Vec::new(),
+            factor: Factor {
+                eliminations: Vec::new(),
+                etas: Vec::new(),
+            },
+        };
+        model.refactor()?;
+        Ok((model, costs, constant))
+    }
+
+    // returns the entering column that no row limits, if there is one
+    fn revised_run(
+        &mut self,
+        model: &mut Revised<T>,
+        costs: &[T],
+        skip_artificial: bool,
+    ) -> Result<Option<usize>, SimplexError<T>> {
+        self.reset_history(model.hash());
+        loop {
+            let c_b: Vec<T> = model.basis.iter().map(|&j| costs[j].clone()).collect();
+            let y = model.factor.btran(&c_b);
+            let mut best: Option<(f64, usize, Vec<T>, Leaving, T)> = None;
+            for (j, cost) in costs.iter().enumerate() {
+                if model.position[j].is_some() || (skip_artificial && model.artificial[j]) {
+                    continue;
+                }
+                let reduced = cost.clone() - model.matrix.dot(j, &y);
+                // variables at the upper bound improve the function by decreasing
+                let improving = if model.at_upper[j] {
+                    reduced > self.tolerance.optimality
+                } else {
+                    reduced < -self.tolerance.optimality.clone()
+                };
+                if !improving {
+                    continue;
+                }
+                let score = match self.pivot_rule {
+                    PivotRule::Bland => -(model.index[j] as f64),
+                    PivotRule::Dantzig => crate::to_f64(reduced.abs()),
+                    PivotRule::GreatestImprovement | PivotRule::SteepestEdge => {
+                        let alpha = model.factor.ftran(&model.dense_column(j));
+                        if self.pivot_rule == PivotRule::SteepestEdge {
+                            let norm = alpha
+                                .iter()
+                                .fold(1f64, |acc, a| acc + crate::to_f64(a.clone() * a.clone()));
+                            crate::to_f64(reduced.abs()) / norm.sqrt()
+                        } else {
+                            match self.revised_ratio(model, j, &alpha) {
+                                Some((_, length)) => crate::to_f64(reduced.abs() * length),
+                                None => f64::INFINITY,
+                            }
+                        }
+                    }
+                };
+                if best.as_ref().is_none_or(|best| score > best.0) {
+                    let alpha = model.factor.ftran(&model.dense_column(j));
+                    match self.revised_ratio(model, j, &alpha) {
+                        Some((leaving, length)) => best = Some((score, j, alpha, leaving, length)),
+                        None => return Ok(Some(j)),
+                    }
+                }
+            }
+            let (_, q, alpha, leaving, length) = match best {
+                Some(best) => best,
+                None => return Ok(None),
+            };
+            self.check_iteration_limit()?;
+            // basic variables move against the entering column
+            let direction = if model.at_upper[q] {
+                -length.clone()
+            } else {
+                length.clone()
+            };
+            for (i, value) in alpha.iter().enumerate() {
+                model.x_b[i] -= direction.clone() * value.clone();
+            }
+            self.iterations += 1;
+            let (r, to_upper) = match leaving {
+                Leaving::Flip => {
+                    debug!("Revised flip of {}", model.names[q]);
+                    model.at_upper[q] = !model.at_upper[q];
+                    continue;
+                }
+                Leaving::Row(r) => (r, false),
+                Leaving::UpperRow(r) => (r, true),
+            };
+            let leaving = model.basis[r];
+            debug!(
+                "Revised pivot: {} enters, {} leaves",
+                model.names[q], model.names[leaving]
+            );
+            let entering_value = if model.at_upper[q] {
+                model.upper[q].clone().unwrap() - length
+            } else {
+                length
+            };
+            model.basis[r] = q;
+            model.position[q] = Some(r);
+            model.position[leaving] = None;
+            model.at_upper[q] = false;
+            model.at_upper[leaving] = to_upper;
+            model.x_b[r] = entering_value;
+            model.factor.update(r, &alpha);
+            if model.factor.etas.len() >= REFACTOR_FREQUENCY {
+                model.refactor()?;
+            }
+            let (entering, leaving) = (model.names[q].clone(), model.names[leaving].clone());
+            self.record_basis(model.hash(), entering, leaving)?;
+        }
+    }
+
+    // bounded ratio test on the entering column alpha = B^-1 a_q
+    fn revised_ratio(&self, model: &Revised<T>, q: usize, alpha: &[T]) -> Option<(Leaving, T)> {
+        let decreasing = model.at_upper[q];
+        let mut best: Option<(Leaving, T)> = None;
+        let mut best_index = usize::MAX;
+        for (i, value) in alpha.iter().enumerate() {
+            if value.abs() <= self.tolerance.pivot {
+                continue;
+            }
+            // how fast the basic variable falls while the entering one moves
+            let rate = if decreasing {
+                -value.clone()
+            } else {
+                value.clone()
+            };
+            let j = model.basis[i];
+            let current = if model.x_b[i] < T::zero() {
+                T::zero()
+            } else {
+                model.x_b[i].clone()
+            };
+            let (length, leaving) = if rate > T::zero() {
+                (current / rate, Leaving::Row(i))
+            } else if let Some(upper) = model.upper[j].clone() {
+                let room = if upper > current {
+                    upper - current
+                } else {
+                    T::zero()
+                };
+                (room / -rate, Leaving::UpperRow(i))
+            } else {
+                continue;
+            };
+            let index = model.index[j];
+            let better = match &best {
+                None => true,
+                Some((_, min)) => {
+                    length < min.clone() - self.tolerance.feasibility.clone()
+                        || ((length.clone() - min.clone()).abs() <= self.tolerance.feasibility
+                            && self.pivot_rule == PivotRule::Bland
+                            && index < best_index)
+                }
+            };
+            if better {
+                best = Some((leaving, length));
+                best_index = index;
+            }
+        }
+        // a bound flip keeps the basis, so it wins the ties
+        if let Some(upper) = model.upper[q].clone() {
+            if best.as_ref().is_none_or(|(_, min)| upper <= *min) {
+                return Some((Leaving::Flip, upper));
+            }
+        }
+        best
+    }
+
+    // pivots artificial variables at zero out of the basis, rows where it is impossible are redundant
+    fn drive_out_artificial(&mut self, model: &mut Revised<T>) -> Result<(), SimplexError<T>> {
+        for r in 0..model.basis.len() {
+            if !model.artificial[model.basis[r]] {
+                continue;
+            }
+            let mut unit = vec![T::zero(); model.basis.len()];
+            unit[r] = T::one();
+            let row = model.factor.btran(&unit);
+            let column = (0..model.matrix.ncols()).find(|&j| {
+                model.position[j].is_none()
+                    && !model.artificial[j]
+                    && model.matrix.dot(j, &row).abs() > self.tolerance.pivot
+            });
+            if let Some(q) = column {
+                let leaving = model.basis[r];
+                debug!("Removing artificial {}", model.names[leaving]);
+                model.basis[r] = q;
+                model.position[q] = Some(r);
+                model.position[leaving] = None;
+                self.iterations += 1;
+                model.refactor()?;
+            }
+        }
+        Ok(())
+    }
+
+    // rebuilds the table for the final basis, so the rest of the crate can read it
+    fn write_back(
+        &mut self,
+        model: &Revised<T>,
+        costs: &[T],
+        constant: T,
+    ) -> Result<(), SimplexError<T>> {
+        let rows: Vec<usize> = (0..model.basis.len())
+            .filter(|&r| !model.artificial[model.basis[r]])
+            .collect();
+        let nonbasic: Vec<usize> = (0..model.matrix.ncols())
+            .filter(|&j| model.position[j].is_none() && !model.artificial[j])
+            .collect();
+        let mut columns = Vec::with_capacity(nonbasic.len());
+        self.fixed_columns.clear();
+        let c_b: Vec<T> = model.basis.iter().map(|&j| costs[j].clone()).collect();
+        let y = model.factor.btran(&c_b);
+        let mut table = SparseMatrix::new(rows.len() + 1);
+        let mut at_upper = Vec::new();
+        for j in nonbasic {
+            let alpha = model.factor.ftran(&model.dense_column(j));
+            let reduced = costs[j].clone() - model.matrix.dot(j, &y);
+            // zero slacks of equalities never enter again, so their columns are kept apart
+            let fixed = self.is_equality(&model.names[j]);
+            // variables at the upper bound are kept complemented in the table
+            let sign = if model.at_upper[j] && !fixed {
+                at_upper.push(model.names[j].clone());
+                -T::one()
+            } else {
+                T::one()
+            };
+            let column: Vec<T> = rows
+                .iter()
+                .map(|&r| sign.clone() * alpha[r].clone())
+                .chain(std::iter::once(-sign.clone() * reduced))
+                .collect();
+            if fixed {
+                self.fixed_columns
+                    .push((model.names[j].clone(), Array1::from_vec(column)));
+            } else {
+                table.push_column(column.into_iter().enumerate());
+                columns.push(j);
+            }
+        }
+        let mut objective = constant;
+        for (j, cost) in costs.iter().enumerate() {
+            objective += cost.clone() * model.value(j);
+        }
+        table.push_column(
+            rows.iter()
+                .map(|&r| model.x_b[r].clone())
+                .chain(std::iter::once(objective))
+                .enumerate(),
+        );
+        // the table stays sparse if the model was given that way
+        if self.sparse.is_some() {
+            self.sparse = Some(table);
+        } else {
+            self.table = table.to_dense();
+        }
+        self.base_var = columns.iter().map(|&j| model.names[j].clone()).collect();
+        self.base_var.push("S".to_string());
+        self.supp_var = rows
+            .iter()
+            .map(|&r| model.names[model.basis[r]].clone())
+            .collect();
+        self.supp_var.push("F".to_string());
+        self.at_upper = at_upper;
+        self.penalty = None;
+        self.artificial.clear();
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use num_rational::Rational64;
+use num_traits::{NumAssign, Signed, ToPrimitive};
+use std::fmt::{Debug, Display, Formatter, Result};
+
+pub type Rational = Rational64;
+// exact arithmetic that can't overflow, for tasks where Rational does
+pub type BigRational = num_rational::BigRational;
+
+pub trait Scalar: Clone + PartialOrd + Signed + NumAssign + ToPrimitive + Display + Debug {
+    // used for every tolerance unless configured otherwise, zero for exact types
+    fn default_tolerance() -> Self;
+    fn fmt_entry(&self, f: &mut Formatter<'_>) -> Result;
+}
+
+impl Scalar for f64 {
+    fn default_tolerance() -> f64 {
+        1e-9
+    }
+    fn fmt_entry(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{:.7}", self)
+    }
+}
+
+impl Scalar for f32 {
+    fn default_tolerance() -> f32 {
+        1e-5
+    }
+    fn fmt_entry(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{:.7}", self)
+    }
+}
+
+impl Scalar for Rational {
+    fn default_tolerance() -> Rational {
+        Rational::from_integer(0)
+    }
+    fn fmt_entry(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Scalar for BigRational {
+    fn default_tolerance() -> BigRational {
+        BigRational::from_integer(0.into())
+    }
+    fn fmt_entry(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self)
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{Scalar, Scaling, Table};
+use log::debug;
+
+// passes of the geometric mean scaling, later ones hardly change the factors
+const GEOMETRIC_PASSES: usize = 4;
+
+impl<T: Scalar> Table<T> {
+    // multiplies every constraint row and every decision variable column by a power of two,
+    // so that the coeffs get close to one without any rounding, done once before the first pivot
+    pub(crate) fn scale(&mut self) {
+        if self.scaling == Scaling::None
+            || !self.row_scale.is_empty()
+            || !self.substitutions.is_empty()
+        {
+            return;
+        }
+        let (nrows, ncols) = (self.constraints.len(), self.variables.len());
+        let entries: Vec<(usize, usize, f64)> = (0..ncols)
+            .flat_map(|j| {
+                self.table_column(j)
+                    .into_iter()
+                    .filter(|(i, value)| *i < nrows && !value.is_zero())
+                    .map(move |(i, value)| (i, j, crate::to_f64(value.abs())))
+            })
+            .collect();
+        let mut rows = vec![1f64; nrows];
+        let mut columns = vec![1f64; ncols];
+        match self.scaling {
+            Scaling::None => return,
+            Scaling::GeometricMean => {
+                for _ in 0..GEOMETRIC_PASSES {
+                    let range = extremes(&entries, nrows, |i, j| (i, rows[i] * columns[j]));
+                    for (i, (min, max)) in range.into_iter().enumerate() {
+                        if max > 0.0 {
+                            rows[i] /= (min * max).sqrt();
+                        }
+                    }
+                    let range = extremes(&entries, ncols, |i, j| (j, rows[i] * columns[j]));
+                    for (j, (min, max)) in range.into_iter().enumerate() {
+                        if max > 0.0 {
+                            columns[j] /= (min * max).sqrt();
+                        }
+                    }
+                }
+            }
+            Scaling::Equilibration => {
+                let range = extremes(&entries, nrows, |i, _| (i, 1.0));
+                for (i, (_, max)) in range.into_iter().enumerate() {
+                    if max > 0.0 {
+                        rows[i] = 1.0 / max;
+                    }
+                }
+                let range = extremes(&entries, ncols, |i, j| (j, rows[i]));
+                for (j, (_, max)) in range.into_iter().enumerate() {
+                    if max > 0.0 {
+                        columns[j] = 1.0 / max;
+                    }
+                }
+            }
+        }
+        let ratio = |rows: &[f64], columns: &[f64]| {
+            let (min, max) = entries
+                .iter()
+                .map(|&(i, j, value)| value * rows[i] * columns[j])
+                .fold((f64::INFINITY, 0f64), |(min, max), i| {
+                    (min.min(i), max.max(i))
+                });
+            max / min
+        };
+        debug!(
+            "Scaling changes the ratio of the largest and the smallest coeff from {:e} to {:e}",
+            ratio(&vec![1.0; nrows], &vec![1.0; ncols]),
+            ratio(&rows, &columns)
+        );
+        self.row_scale = rows.into_iter().map(power_of_two).collect();
+        self.column_scale = columns.into_iter().map(power_of_two).collect();
+        debug!("Row scale factors: {:?}", self.row_scale);
+        debug!("Column scale factors: {:?}", self.column_scale);
+        // the function row and the free column keep factor one
+        let mut row_factors = self.row_scale.clone();
+        row_factors.resize(self.shape().0, T::one());
+        let mut column_factors = self.column_scale.clone();
+        column_factors.resize(self.shape().1, T::one());
+        match self.sparse.as_mut() {
+            Some(sparse) => sparse.scale(&row_factors, &column_factors),
+            None => {
+                for ((i, j), value) in self.table.indexed_iter_mut() {
+                    *value *= row_factors[i].clone() * column_factors[j].clone();
+                }
+            }
+        }
+    }
+
+    // factor of the constraint with the given index
+    pub(crate) fn row_factor(&self, row: usize) -> T {
+        self.row_scale.get(row).cloned().unwrap_or(T::one())
+    }
+
+    // factor of the decision variable with the given index
+    pub(crate) fn column_factor(&self, column: usize) -> T {
+        self.column_scale.get(column).cloned().unwrap_or(T::one())
+    }
+}
+
+// (smallest, largest) scaled absolute coeff for every index the entries are grouped by
+fn extremes<F: Fn(usize, usize) -> (usize, f64)>(
+    entries: &[(usize, usize, f64)],
+    len: usize,
+    group: F,
+) -> Vec<(f64, f64)> {
+    let mut range = vec![(f64::INFINITY, 0f64); len];
+    for &(i, j, value) in entries {
+        let (k, factor) = group(i, j);
+        let value = value * factor;
+        range[k] = (range[k].0.min(value), range[k].1.max(value));
+    }
+    range
+}
+
+// nearest power of two, multiplying by it is exact
+fn power_of_two<T: Scalar>(factor: f64) -> T {
+    let exponent = factor.log2().round() as i32;
+    let two = T::one() + T::one();
+    let mut result = T::one();
+    for _ in 0..exponent.unsigned_abs() {
+        if exponent > 0 {
+            result *= two.clone();
+        } else {
+            result /= two.clone();
+        }
+    }
+    result
+}
@@ -0,0 +1,193 @@
+use crate::{Scalar, Sense, SimplexError, Table};
+use ndarray::Array1;
+
+// post-optimal analysis of an optimised table, everything is given in terms of the task objective
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sensitivity<T = f64> {
+    pub variable_names: Vec<String>,
+    pub constraint_names: Vec<String>,
+    // change of the objective per unit increase of the right-hand side of every constraint
+    pub shadow_prices: Vec<T>,
+    // change of the objective per unit increase of every variable, zero for basic ones
+    pub reduced_costs: Vec<T>,
+    // allowable (decrease, increase) of the coefficients within which the basis stays optimal,
+    // None if the change is unlimited
+    pub objective_ranges: Vec<(Option<T>, Option<T>)>,
+    pub rhs_ranges: Vec<(Option<T>, Option<T>)>,
+}
+
+impl<T: Scalar> Table<T> {
+    // reads the report from the final table, the table has to be optimal
+    pub fn sensitivity(&self) -> Result<Sensitivity<T>, SimplexError<T>> {
+        let (nrows, ncols) = self.shape();
+        let function: Vec<T> = (0..ncols - 1).map(|j| self.entry(nrows - 1, j)).collect();
+        if self.substitutions.is_empty()
+            || function.iter().any(|f| *f > self.tolerance.optimality)
+            || (0..nrows - 1).any(|i| self.free_coeff(i) < -self.tolerance.feasibility.clone())
+        {
+            return Err(SimplexError::InvalidDataError);
+        }
+        // the function row is minimised, so it is the objective of a minimisation task
+        // and its negative otherwise
+        let sign = if self.minimisation_task {
+            T::one()
+        } else {
+            -T::one()
+        };
+        // a scaled variable x' = x / factor changes the objective factor times faster
+        let reduced_costs = self
+            .substitutions
+            .iter()
+            .enumerate()
+            .map(|(j, substitution)| {
+                let (name, part) = match (&substitution.positive, &substitution.negative) {
+                    (Some(positive), _) => (positive, T::one()),
+                    (None, Some(negative)) => (negative, -T::one()),
+                    (None, None) => return T::zero(),
+                };
+                let cost = match self.base_var.iter().position(|i| i == name) {
+                    Some(k) if self.at_upper.contains(name) => {
+                        sign.clone() * part * function[k].clone()
+                    }
+                    Some(k) => -sign.clone() * part * function[k].clone(),
+                    None => T::zero(),
+                };
+                cost / self.column_factor(j)
+            })
+            .collect();
+        let objective_ranges = (0..self.variables.len())
+            .map(|i| {
+                let range = self.function_range(&self.variable_expression(i), &function);
+                let range = if self.minimisation_task {
+                    range
+                } else {
+                    (range.1, range.0)
+                };
+                let factor = self.column_factor(i);
+                (
+                    range.0.map(|i| i / factor.clone()),
+                    range.1.map(|i| i / factor),
+                )
+            })
+            .collect();
+        let mut shadow_prices = Vec::with_capacity(self.constraints.len());
+        let mut rhs_ranges = Vec::with_capacity(self.constraints.len());
+        for (k, (name, sense)) in self.constraints.iter().zip(self.senses.iter()).enumerate() {
+            // >= rows are negated in the table
+            let direction = if *sense == Sense::GreaterEqual {
+                T::one()
+            } else {
+                -T::one()
+            };
+            let column = self
+                .base_var
+                .iter()
+                .position(|i| i == name)
+                .map(|j| Array1::from_vec((0..nrows).map(|i| self.entry(i, j)).collect()))
+                .or_else(|| {
+                    self.fixed_columns
+                        .iter()
+                        .find(|(i, _)| i == name)
+                        .map(|(_, column)| column.clone())
+                });
+            let (price, range) = match column {
+                Some(column) => (
+                    // moving the right-hand side up moves the slack down
+                    -direction.clone() * sign.clone() * column[nrows - 1].clone(),
+                    self.rhs_range(&column.mapv(|i| -direction.clone() * i)),
+                ),
+                None => match self.supp_var.iter().position(|i| i == name) {
+                    Some(row) => {
+                        let mut column = Array1::zeros(nrows);
+                        column[row] = -direction;
+                        (T::zero(), self.rhs_range(&column))
+                    }
+                    // the row was removed as a combination of the others
+                    None => (T::zero(), (Some(T::zero()), Some(T::zero()))),
+                },
+            };
+            // a scaled row moves factor times faster than its right-hand side
+            let factor = self.row_factor(k);
+            shadow_prices.push(price * factor.clone());
+            rhs_ranges.push((
+                range.0.map(|i| i / factor.clone()),
+                range.1.map(|i| i / factor),
+            ));
+        }
+        Ok(Sensitivity {
+            variable_names: self.variables.clone(),
+            constraint_names: self.constraints.clone(),
+            shadow_prices,
+            reduced_costs,
+            objective_ranges,
+            rhs_ranges,
+        })
+    }
+
+    // allowable (decrease, increase) of eps for which adding eps * expression
+    // to the minimised function keeps the function row optimal
+    fn function_range(&self, expression: &Array1<T>, function: &[T]) -> (Option<T>, Option<T>) {
+        let mut range = (None, None);
+        for (j, f) in function.iter().enumerate() {
+            let coeff = expression[j].clone();
+            if coeff.abs() <= self.tolerance.pivot {
+                continue;
+            }
+            let room = if f.is_negative() {
+                -f.clone()
+            } else {
+                T::zero()
+            };
+            tighten(&mut range, room / coeff.clone(), coeff.is_positive());
+        }
+        (range.0.map(|i: T| -i), range.1)
+    }
+
+    // allowable (decrease, increase) of a right-hand side moving the free column by
+    // delta * column, every basic variable has to stay within its bounds
+    fn rhs_range(&self, column: &Array1<T>) -> (Option<T>, Option<T>) {
+        let mut range = (None, None);
+        for (i, name) in self.supp_var[..column.len() - 1].iter().enumerate() {
+            let coeff = column[i].clone();
+            if coeff.abs() <= self.tolerance.pivot {
+                continue;
+            }
+            let value = self.free_coeff(i);
+            let value = if value.is_negative() {
+                T::zero()
+            } else {
+                value
+            };
+            tighten(
+                &mut range,
+                -value.clone() / coeff.clone(),
+                coeff.is_negative(),
+            );
+            let upper = if self.is_equality(name) {
+                Some(T::zero())
+            } else {
+                self.upper.get(name).cloned()
+            };
+            if let Some(upper) = upper {
+                let room = if upper > value {
+                    upper - value
+                } else {
+                    T::zero()
+                };
+                tighten(&mut range, room / coeff.clone(), coeff.is_positive());
+            }
+        }
+        (range.0.map(|i: T| -i), range.1)
+    }
+}
+
+// applies a bound on delta to the (lowest, highest) range
+fn tighten<T: Scalar>(range: &mut (Option<T>, Option<T>), bound: T, upper: bool) {
+    if upper {
+        if range.1.as_ref().is_none_or(|i| bound < *i) {
+            range.1 = Some(bound);
+        }
+    } else if range.0.as_ref().is_none_or(|i| bound > *i) {
+        range.0 = Some(bound);
+    }
+}
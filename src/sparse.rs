@@ -0,0 +1,221 @@
+use crate::{Scalar, SimplexError};
+use ndarray::Array2;
+
+// column-compressed sparse matrix, entries of every column are sorted by row
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseMatrix<T = f64> {
+    nrows: usize,
+    starts: Vec<usize>,
+    rows: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<T: Scalar> SparseMatrix<T> {
+    pub(crate) fn new(nrows: usize) -> SparseMatrix<T> {
+        SparseMatrix {
+            nrows,
+            starts: vec![0],
+            rows: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    // (row, column, value) entries in any order, repeated entries are summed
+    pub fn from_triplets(
+        nrows: usize,
+        ncols: usize,
+        triplets: &[(usize, usize, T)],
+    ) -> Result<SparseMatrix<T>, SimplexError<T>> {
+        if triplets.iter().any(|&(i, j, _)| i >= nrows || j >= ncols) {
+            return Err(SimplexError::InvalidDataError);
+        }
+        let mut sorted = triplets.to_vec();
+        sorted.sort_by_key(|&(i, j, _)| (j, i));
+        let mut matrix = SparseMatrix::new(nrows);
+        let mut entries = sorted.into_iter().peekable();
+        for j in 0..ncols {
+            let mut column: Vec<(usize, T)> = Vec::new();
+            while let Some((i, _, value)) = entries.next_if(|&(_, c, _)| c == j) {
+                match column.last_mut() {
+                    Some(last) if last.0 == i => last.1 += value,
+                    _ => column.push((i, value)),
+                }
+            }
+            matrix.push_column(column);
+        }
+        Ok(matrix)
+    }
+
+    // compressed columns: entries of column j are at col_starts[j]..col_starts[j + 1]
+    pub fn from_csc(
+        nrows: usize,
+        col_starts: &[usize],
+        row_indices: &[usize],
+        values: &[T],
+    ) -> Result<SparseMatrix<T>, SimplexError<T>> {
+        let triplets = compressed_triplets(col_starts, row_indices, values)?;
+        SparseMatrix::from_triplets(
+            nrows,
+            col_starts.len() - 1,
+            &triplets
+                .into_iter()
+                .map(|(j, i, value)| (i, j, value))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    // compressed rows: entries of row i are at row_starts[i]..row_starts[i + 1]
+    pub fn from_csr(
+        ncols: usize,
+        row_starts: &[usize],
+        col_indices: &[usize],
+        values: &[T],
+    ) -> Result<SparseMatrix<T>, SimplexError<T>> {
+        let triplets = compressed_triplets(row_starts, col_indices, values)?;
+        SparseMatrix::from_triplets(row_starts.len() - 1, ncols, &triplets)
+    }
+
+    pub fn from_dense(matrix: &Array2<T>) -> SparseMatrix<T> {
+        let mut sparse = SparseMatrix::new(matrix.nrows());
+        for column in matrix.columns() {
+            sparse.push_column(column.iter().cloned().enumerate());
+        }
+        sparse
+    }
+
+    pub fn to_dense(&self) -> Array2<T> {
+        let mut matrix = Array2::zeros((self.nrows, self.ncols()));
+        for j in 0..self.ncols() {
+            for (i, value) in self.column(j) {
+                matrix[[i, j]] = value;
+            }
+        }
+        matrix
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.starts.len() - 1
+    }
+
+    // number of stored non-zero entries
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> T {
+        let range = self.starts[column]..self.starts[column + 1];
+        match self.rows[range.clone()].binary_search(&row) {
+            Ok(k) => self.values[range.start + k].clone(),
+            Err(_) => T::zero(),
+        }
+    }
+
+    pub fn column(&self, j: usize) -> impl Iterator<Item = (usize, T)> + '_ {
+        let range = self.starts[j]..self.starts[j + 1];
+        self.rows[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.values[range].iter().cloned())
+    }
+
+    // entries have to come in increasing row order
+    pub(crate) fn push_column<I: IntoIterator<Item = (usize, T)>>(&mut self, column: I) {
+        for (i, value) in column {
+            if !value.is_zero() {
+                self.rows.push(i);
+                self.values.push(value);
+            }
+        }
+        self.starts.push(self.rows.len());
+    }
+
+    pub(crate) fn pop_column(&mut self) -> Vec<(usize, T)> {
+        let start = self.starts[self.ncols() - 1];
+        self.starts.pop();
+        self.rows
+            .drain(start..)
+            .zip(self.values.drain(start..))
+            .collect()
+    }
+
+    pub(crate) fn add(&mut self, row: usize, column: usize, value: T) {
+        let range = self.starts[column]..self.starts[column + 1];
+        match self.rows[range.clone()].binary_search(&row) {
+            Ok(k) => self.values[range.start + k] += value,
+            Err(k) => {
+                self.rows.insert(range.start + k, row);
+                self.values.insert(range.start + k, value);
+                for start in self.starts[column + 1..].iter_mut() {
+                    *start += 1;
+                }
+            }
+        }
+    }
+
+    pub(crate) fn dot(&self, j: usize, vector: &[T]) -> T {
+        self.column(j)
+            .fold(T::zero(), |acc, (i, value)| acc + value * vector[i].clone())
+    }
+
+    pub(crate) fn negate_row(&mut self, row: usize) {
+        for (i, value) in self.rows.iter().zip(self.values.iter_mut()) {
+            if *i == row {
+                *value = -value.clone();
+            }
+        }
+    }
+
+    pub(crate) fn negate_column(&mut self, column: usize) {
+        for value in self.values[self.starts[column]..self.starts[column + 1]].iter_mut() {
+            *value = -value.clone();
+        }
+    }
+
+    // multiplies every entry by the factors of its row and its column
+    pub(crate) fn scale(&mut self, rows: &[T], columns: &[T]) {
+        for (j, factor) in columns.iter().enumerate() {
+            for k in self.starts[j]..self.starts[j + 1] {
+                self.values[k] *= rows[self.rows[k]].clone() * factor.clone();
+            }
+        }
+    }
+
+    // inserts a column before the existing one with the given index
+    pub(crate) fn insert_column(&mut self, index: usize, column: Vec<(usize, T)>) {
+        let mut tail = Vec::new();
+        while self.ncols() > index {
+            tail.push(self.pop_column());
+        }
+        self.push_column(column);
+        while let Some(column) = tail.pop() {
+            self.push_column(column);
+        }
+    }
+}
+
+// checks the compressed arrays and lists their entries as (outer, inner, value)
+fn compressed_triplets<T: Scalar>(
+    starts: &[usize],
+    indices: &[usize],
+    values: &[T],
+) -> Result<Vec<(usize, usize, T)>, SimplexError<T>> {
+    if starts.is_empty()
+        || starts[0] != 0
+        || starts.windows(2).any(|i| i[0] > i[1])
+        || starts[starts.len() - 1] != indices.len()
+        || indices.len() != values.len()
+    {
+        return Err(SimplexError::InvalidDataError);
+    }
+    let mut triplets = Vec::with_capacity(values.len());
+    for outer in 0..starts.len() - 1 {
+        for k in starts[outer]..starts[outer + 1] {
+            triplets.push((outer, indices[k], values[k].clone()));
+        }
+    }
+    Ok(triplets)
+}
@@ -0,0 +1,84 @@
+use ndarray::{arr1, arr2};
+use simplex_method::{Algorithm, Sense, SimplexError, Table};
+
+#[test]
+fn split_names_keep_clear_of_user_names() {
+    // max x + y, x <= 1, y <= 4, with x free and y named like its negative part
+    let mut table: Table = Table::new(
+        arr2(&[[1.0, 0.0], [0.0, 1.0]]),
+        arr1(&[1.0, 4.0]),
+        arr1(&[1.0, 1.0]),
+        false,
+    )
+    .unwrap();
+    table.set_names(&["x", "x-"], &["a", "b"]).unwrap();
+    table.lower_bounds[0] = None;
+    let solution = table.optimise().unwrap();
+    assert_eq!(solution.variables, vec![1.0, 4.0]);
+}
+
+#[test]
+fn shifted_names_keep_clear_of_user_names() {
+    // min x + y, x + y >= 3, with x >= 1 and y named like the shifted x
+    let mut table: Table = Table::new(
+        arr2(&[[-1.0, -1.0]]),
+        arr1(&[-3.0]),
+        arr1(&[1.0, 2.0]),
+        true,
+    )
+    .unwrap();
+    table.set_names(&["x", "x'"], &["a"]).unwrap();
+    table.lower_bounds = vec![Some(1.0), Some(0.0)];
+    let solution = table.optimise().unwrap();
+    assert_eq!(solution.variables, vec![3.0, 0.0]);
+}
+
+#[test]
+fn free_variable_ray_starts_at_the_vertex() {
+    // max y + x, y <= 4, with x free and y named like its negative part
+    let mut table: Table =
+        Table::new(arr2(&[[1.0, 0.0]]), arr1(&[4.0]), arr1(&[1.0, 1.0]), false).unwrap();
+    table.set_names(&["x-", "x"], &["a"]).unwrap();
+    table.lower_bounds[1] = None;
+    match table.optimise() {
+        Err(SimplexError::UnlimitedError(Some(ray))) => {
+            assert_eq!(ray.vertex, vec![4.0, 0.0]);
+            assert_eq!(ray.direction, vec![0.0, 1.0]);
+            let (constr_coeff, func_coeff) = (arr2(&[[1.0, 0.0]]), arr1(&[1.0, 1.0]));
+            let check = |upper: Option<f64>| {
+                ray.check(
+                    &constr_coeff,
+                    &func_coeff,
+                    false,
+                    &[Sense::LessEqual],
+                    &[Some(0.0), None],
+                    &[None, upper],
+                    1e-9,
+                )
+            };
+            assert!(check(None));
+            // the ray doesn't hold if the caller's x has an upper bound
+            assert!(!check(Some(10.0)));
+        }
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn bound_flips_leave_variables_at_upper() {
+    // max x + 2y, x + y <= 10, with x <= 3 and y <= 4, no row gets tight
+    for algorithm in [Algorithm::Tableau, Algorithm::Revised] {
+        let mut table: Table =
+            Table::new(arr2(&[[1.0, 1.0]]), arr1(&[10.0]), arr1(&[1.0, 2.0]), false).unwrap();
+        table.set_names(&["x", "y"], &["a"]).unwrap();
+        table.upper_bounds = vec![Some(3.0), Some(4.0)];
+        table.algorithm = algorithm;
+        let solution = table.optimise().unwrap();
+        assert_eq!(solution.variables, vec![3.0, 4.0]);
+        assert_eq!(solution.slacks, vec![3.0]);
+        let mut at_upper = solution.at_upper.clone();
+        at_upper.sort();
+        assert_eq!(at_upper, vec!["x".to_string(), "y".to_string()]);
+        assert_eq!(solution.basis, vec!["a".to_string()]);
+    }
+}
@@ -0,0 +1,31 @@
+use ndarray::{arr1, arr2};
+use simplex_method::{Sense, SimplexError, Table};
+
+#[test]
+fn dual_simplex_restores_feasibility() {
+    // min x + 2y, x + y >= 2, x - y <= 1, optimal at x = 1.5, y = 0.5
+    let mut table: Table = Table::new(
+        arr2(&[[1.0, 1.0], [1.0, -1.0]]),
+        arr1(&[2.0, 1.0]),
+        arr1(&[1.0, 2.0]),
+        true,
+    )
+    .unwrap();
+    table
+        .set_senses(&[Sense::GreaterEqual, Sense::LessEqual])
+        .unwrap();
+    let solution = table.dual_optimise().unwrap();
+    assert_eq!(solution.objective, 2.5);
+    assert_eq!(solution.variables, vec![1.5, 0.5]);
+}
+
+#[test]
+fn dual_simplex_reports_a_row_that_cant_hold() {
+    // min x + y, x + y <= -1, no row coeff can take the negative free coeff
+    let mut table: Table =
+        Table::new(arr2(&[[1.0, 1.0]]), arr1(&[-1.0]), arr1(&[1.0, 1.0]), true).unwrap();
+    match table.dual_optimise() {
+        Err(SimplexError::DualUnlimitedError) => {}
+        other => panic!("{:?}", other),
+    }
+}
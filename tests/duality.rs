@@ -0,0 +1,129 @@
+use ndarray::{arr1, arr2, Array1, Array2};
+use simplex_method::{Certificate, Solution, Table, Violation};
+
+// max 3x + 5y, x <= 4, 2y <= 12, 3x + 2y <= 18, optimal at x = 2, y = 6
+fn textbook() -> (Array2<f64>, Array1<f64>, Array1<f64>) {
+    (
+        arr2(&[[1.0, 0.0], [0.0, 2.0], [3.0, 2.0]]),
+        arr1(&[4.0, 12.0, 18.0]),
+        arr1(&[3.0, 5.0]),
+    )
+}
+
+#[test]
+fn dual_of_maximisation_is_verified() {
+    let (constr_coeff, constr_val, func_coeff) = textbook();
+    let duality = Table::solve_with_dual(
+        constr_coeff.clone(),
+        constr_val.clone(),
+        func_coeff.clone(),
+        false,
+    )
+    .unwrap();
+    assert_eq!(duality.primal.objective, 36.0);
+    assert_eq!(duality.dual.objective, 36.0);
+    assert_eq!(duality.shadow_prices, vec![0.0, 1.5, 1.0]);
+    let certificate = duality
+        .verify(&constr_coeff, &constr_val, &func_coeff, false)
+        .unwrap();
+    assert!(certificate.is_optimal());
+}
+
+#[test]
+fn dual_of_minimisation_is_verified() {
+    // min -2x - y, x + y <= 4, x <= 3, optimal at x = 3, y = 1
+    let (constr_coeff, constr_val, func_coeff) = (
+        arr2(&[[1.0, 1.0], [1.0, 0.0]]),
+        arr1(&[4.0, 3.0]),
+        arr1(&[-2.0, -1.0]),
+    );
+    let duality = Table::solve_with_dual(
+        constr_coeff.clone(),
+        constr_val.clone(),
+        func_coeff.clone(),
+        true,
+    )
+    .unwrap();
+    assert_eq!(duality.primal.objective, -7.0);
+    assert_eq!(duality.dual.objective, -7.0);
+    let certificate = duality
+        .verify(&constr_coeff, &constr_val, &func_coeff, true)
+        .unwrap();
+    assert!(certificate.is_optimal());
+}
+
+fn point(variables: &[f64], names: &[&str]) -> Solution {
+    Solution {
+        objective: 0.0,
+        variables: variables.to_vec(),
+        slacks: Vec::new(),
+        variable_names: ["x", "y"].iter().map(|i| i.to_string()).collect(),
+        constraint_names: names.iter().map(|i| i.to_string()).collect(),
+        basis: Vec::new(),
+        at_upper: Vec::new(),
+        iterations: 0,
+    }
+}
+
+#[test]
+fn certificate_reports_every_violation() {
+    let (constr_coeff, constr_val, func_coeff) = textbook();
+    let rows = ["a", "b", "c"];
+    let check = |x: &[f64], y: &[f64]| {
+        Certificate::check(
+            &constr_coeff,
+            &constr_val,
+            &func_coeff,
+            false,
+            &point(x, &rows),
+            &point(y, &rows),
+            1e-9,
+        )
+        .unwrap()
+    };
+    assert!(check(&[2.0, 6.0], &[0.0, 1.5, 1.0]).is_optimal());
+    for (x, y, violation) in [
+        // 3x + 2y is 24
+        (
+            [4.0, 6.0],
+            [0.0, 1.5, 1.0],
+            Violation::PrimalConstraint("c".to_string(), 6.0),
+        ),
+        (
+            [-1.0, 6.0],
+            [0.0, 1.5, 1.0],
+            Violation::PrimalSign("x".to_string(), 1.0),
+        ),
+        // 2 * 0 + 2 * 1 is less than the coeff 5 of y
+        (
+            [2.0, 6.0],
+            [0.0, 0.0, 1.0],
+            Violation::DualConstraint("y".to_string(), 3.0),
+        ),
+        (
+            [2.0, 6.0],
+            [0.0, 1.5, -1.0],
+            Violation::DualSign("c".to_string(), 1.0),
+        ),
+        // x <= 4 is not tight but has a price
+        (
+            [2.0, 6.0],
+            [1.0, 1.5, 1.0],
+            Violation::ConstraintSlackness("a".to_string(), 2.0),
+        ),
+        // the dual constraint of x is not tight but x is positive
+        (
+            [2.0, 6.0],
+            [1.0, 1.5, 1.0],
+            Violation::VariableSlackness("x".to_string(), 2.0),
+        ),
+    ] {
+        let certificate = check(&x, &y);
+        assert!(
+            certificate.violations.contains(&violation),
+            "{:?} not in {:?}",
+            violation,
+            certificate.violations
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use ndarray::{Array1, Array2, Axis};
+use simplex_method::{Algorithm, BigRational, Sense, Table};
+
+// H x = H 1 for the Hilbert matrix H, the only solution is x = 1; the entries of
+// the inverse overflow Rational and lose all precision in f64 around that size
+fn hilbert(n: usize) -> Table<BigRational> {
+    let h = Array2::from_shape_fn((n, n), |(i, j)| {
+        BigRational::new(1.into(), (i as i64 + j as i64 + 1).into())
+    });
+    let b = h.sum_axis(Axis(1));
+    let mut table = Table::new(
+        h,
+        b,
+        Array1::from_elem(n, BigRational::from_integer(1.into())),
+        false,
+    )
+    .unwrap();
+    table.set_senses(&vec![Sense::Equal; n]).unwrap();
+    table
+}
+
+#[test]
+fn hilbert_system_is_solved_exactly() {
+    for algorithm in [Algorithm::Tableau, Algorithm::Revised] {
+        let mut table = hilbert(12);
+        table.algorithm = algorithm;
+        let solution = table.optimise().unwrap();
+        assert_eq!(solution.objective, BigRational::from_integer(12.into()));
+        assert!(solution
+            .variables
+            .iter()
+            .all(|x| *x == BigRational::from_integer(1.into())));
+    }
+}
@@ -0,0 +1,145 @@
+use ndarray::{arr1, arr2};
+use simplex_method::{Algorithm, Farkas, Feasibility, Sense, SimplexError, Table};
+
+// max x + y, x - y <= 1, x - y >= 3
+fn inconsistent(feasibility: Feasibility, algorithm: Algorithm) -> Table {
+    let mut table: Table = Table::new(
+        arr2(&[[1.0, -1.0], [1.0, -1.0]]),
+        arr1(&[1.0, 3.0]),
+        arr1(&[1.0, 1.0]),
+        false,
+    )
+    .unwrap();
+    table
+        .set_senses(&[Sense::LessEqual, Sense::GreaterEqual])
+        .unwrap();
+    table.feasibility = feasibility;
+    table.algorithm = algorithm;
+    table
+}
+
+fn check(farkas: &Farkas) {
+    let constr_coeff = arr2(&[[1.0, -1.0], [1.0, -1.0]]);
+    assert!(farkas.check(
+        &constr_coeff,
+        &arr1(&[1.0, 3.0]),
+        &[Sense::LessEqual, Sense::GreaterEqual],
+        &[Some(0.0); 2],
+        &[None; 2],
+        1e-9
+    ));
+}
+
+#[test]
+fn big_m_reports_infeasible_before_unlimited() {
+    match inconsistent(Feasibility::BigM, Algorithm::Tableau).optimise() {
+        Err(SimplexError::ArtificialSumError(_, farkas)) => check(&farkas),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn two_phase_reports_infeasible() {
+    for algorithm in [Algorithm::Tableau, Algorithm::Revised] {
+        match inconsistent(Feasibility::TwoPhase, algorithm).optimise() {
+            Err(SimplexError::ArtificialSumError(_, farkas)) => check(&farkas),
+            other => panic!("{:?}", other),
+        }
+    }
+}
+
+#[test]
+fn make_acceptable_reports_infeasible() {
+    match inconsistent(Feasibility::MakeAcceptable, Algorithm::Tableau).optimise() {
+        Err(SimplexError::NoSolutionsError(Some(farkas))) => check(&farkas),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn phase_one_proves_conflicting_equalities() {
+    // x + y = 2, x - y = 0, x <= 0.5 with y <= 3
+    let constr_coeff = arr2(&[[1.0, 1.0], [1.0, -1.0], [1.0, 0.0]]);
+    let constr_val = arr1(&[2.0, 0.0, 0.5]);
+    for feasibility in [Feasibility::TwoPhase, Feasibility::BigM] {
+        for algorithm in [Algorithm::Tableau, Algorithm::Revised] {
+            let mut table: Table = Table::new(
+                constr_coeff.clone(),
+                constr_val.clone(),
+                arr1(&[1.0, 1.0]),
+                true,
+            )
+            .unwrap();
+            table
+                .set_senses(&[Sense::Equal, Sense::Equal, Sense::LessEqual])
+                .unwrap();
+            table.upper_bounds[1] = Some(3.0);
+            table.feasibility = feasibility;
+            table.algorithm = algorithm;
+            match table.optimise() {
+                Err(SimplexError::ArtificialSumError(_, farkas)) => {
+                    assert!(farkas.check(
+                        &constr_coeff,
+                        &constr_val,
+                        &[Sense::Equal, Sense::Equal, Sense::LessEqual],
+                        &[Some(0.0); 2],
+                        &[None, Some(3.0)],
+                        1e-9
+                    ))
+                }
+                other => panic!("{:?}", other),
+            }
+        }
+    }
+}
+
+#[test]
+fn big_m_agrees_with_two_phase() {
+    // max 3x + 2y, x + y <= 4, x + 3y >= 6, x - y = 1
+    let solve = |feasibility| {
+        let mut table: Table = Table::new(
+            arr2(&[[1.0, 1.0], [1.0, 3.0], [1.0, -1.0]]),
+            arr1(&[4.0, 6.0, 1.0]),
+            arr1(&[3.0, 2.0]),
+            false,
+        )
+        .unwrap();
+        table
+            .set_senses(&[Sense::LessEqual, Sense::GreaterEqual, Sense::Equal])
+            .unwrap();
+        table.feasibility = feasibility;
+        table.optimise().unwrap()
+    };
+    let big_m = solve(Feasibility::BigM);
+    let two_phase = solve(Feasibility::TwoPhase);
+    assert!((big_m.objective - 10.5).abs() < 1e-9);
+    assert!((two_phase.objective - 10.5).abs() < 1e-9);
+    assert!((big_m.variables[0] - 2.5).abs() < 1e-9);
+    assert!((big_m.variables[1] - 1.5).abs() < 1e-9);
+}
+
+#[test]
+fn farkas_check_reads_senses_and_bounds_from_the_task() {
+    // x <= 5 holds for x = 0, only x = 5 with 0 <= x <= 0 is infeasible
+    let farkas = Farkas {
+        multipliers: vec![-1.0],
+        constraint_names: vec!["2".to_string()],
+    };
+    let (constr_coeff, constr_val) = (arr2(&[[1.0]]), arr1(&[5.0]));
+    assert!(!farkas.check(
+        &constr_coeff,
+        &constr_val,
+        &[Sense::LessEqual],
+        &[Some(0.0)],
+        &[None],
+        1e-9
+    ));
+    assert!(farkas.check(
+        &constr_coeff,
+        &constr_val,
+        &[Sense::Equal],
+        &[Some(0.0)],
+        &[Some(0.0)],
+        1e-9
+    ));
+}
@@ -0,0 +1,42 @@
+use ndarray::{arr1, arr2};
+use simplex_method::{Sense, SimplexError, Table};
+
+#[test]
+fn conflicting_bounds_are_not_blamed_on_rows() {
+    // max x + y, x + y <= 4, with 3 <= x <= 1
+    let mut table: Table =
+        Table::new(arr2(&[[1.0, 1.0]]), arr1(&[4.0]), arr1(&[1.0, 1.0]), false).unwrap();
+    table.lower_bounds[0] = Some(3.0);
+    table.upper_bounds[0] = Some(1.0);
+    assert!(matches!(
+        table.iis(),
+        Err(SimplexError::NoSolutionsError(None))
+    ));
+}
+
+#[test]
+fn iis_keeps_only_the_conflicting_pair() {
+    // max x + y, x + y <= 10, x >= 5, y <= 8, x <= 3, x + 2y <= 20
+    let mut table: Table = Table::new(
+        arr2(&[[1.0, 1.0], [1.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 2.0]]),
+        arr1(&[10.0, 5.0, 8.0, 3.0, 20.0]),
+        arr1(&[1.0, 1.0]),
+        false,
+    )
+    .unwrap();
+    table
+        .set_names(&["x", "y"], &["total", "least", "most_y", "most_x", "mix"])
+        .unwrap();
+    table
+        .set_senses(&[
+            Sense::LessEqual,
+            Sense::GreaterEqual,
+            Sense::LessEqual,
+            Sense::LessEqual,
+            Sense::LessEqual,
+        ])
+        .unwrap();
+    let mut iis = table.iis().unwrap();
+    iis.sort();
+    assert_eq!(iis, vec!["least".to_string(), "most_x".to_string()]);
+}
@@ -0,0 +1,22 @@
+use ndarray::{arr1, arr2};
+use simplex_method::{SimplexError, Table};
+
+#[test]
+fn short_constraint_values_are_rejected() {
+    let table: Result<Table, _> = Table::new(
+        arr2(&[[1.0, 1.0], [1.0, 0.0], [0.0, 1.0]]),
+        arr1(&[4.0]),
+        arr1(&[1.0, 1.0]),
+        false,
+    );
+    assert!(matches!(table, Err(SimplexError::InvalidDataError)));
+}
+
+#[test]
+fn function_of_wrong_length_is_rejected() {
+    for func_coeff in [arr1(&[1.0]), arr1(&[1.0, 1.0, 1.0])] {
+        let table: Result<Table, _> =
+            Table::new(arr2(&[[1.0, 1.0]]), arr1(&[4.0]), func_coeff, false);
+        assert!(matches!(table, Err(SimplexError::InvalidDataError)));
+    }
+}
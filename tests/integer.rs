@@ -0,0 +1,79 @@
+use ndarray::{arr1, arr2, Array1, Array2};
+use simplex_method::{Integrality, NodeSelection, SimplexError, Table};
+
+// max 5x + 4y + 3z, 6x + 4y + 3z <= 24, x + 2y + 3z <= 6, 3x + y + 2z <= 11,
+// every variable integer
+fn model() -> (Array2<f64>, Array1<f64>, Array1<f64>) {
+    (
+        arr2(&[[6.0, 4.0, 3.0], [1.0, 2.0, 3.0], [3.0, 1.0, 2.0]]),
+        arr1(&[24.0, 6.0, 11.0]),
+        arr1(&[5.0, 4.0, 3.0]),
+    )
+}
+
+fn integer_table() -> Table {
+    let (constr_coeff, constr_val, func_coeff) = model();
+    let mut table = Table::new(constr_coeff, constr_val, func_coeff, false).unwrap();
+    table.integrality = vec![Integrality::Integer; 3];
+    table
+}
+
+// best objective over every integer point in the box the rows allow
+fn brute_force() -> f64 {
+    let (constr_coeff, constr_val, func_coeff) = model();
+    let mut best = f64::NEG_INFINITY;
+    for x in 0..=6 {
+        for y in 0..=6 {
+            for z in 0..=6 {
+                let point = arr1(&[x as f64, y as f64, z as f64]);
+                if constr_coeff
+                    .dot(&point)
+                    .iter()
+                    .zip(constr_val.iter())
+                    .all(|(row, value)| row <= value)
+                {
+                    best = best.max(func_coeff.dot(&point));
+                }
+            }
+        }
+    }
+    best
+}
+
+#[test]
+fn branch_and_bound_finds_the_integer_optimum() {
+    let expected = brute_force();
+    for node_selection in [NodeSelection::DepthFirst, NodeSelection::BestBound] {
+        let mut table = integer_table();
+        table.node_selection = node_selection;
+        let result = table.branch_and_bound().unwrap();
+        assert_eq!(result.solution.objective, expected);
+        assert!(result.solution.variables.iter().all(|x| *x == x.round()));
+        assert_eq!(result.gap, 0.0);
+        // the continuous optimum is fractional, so the root had to be branched
+        assert!(result.nodes > 1);
+    }
+}
+
+#[test]
+fn gomory_cuts_reach_the_integer_optimum() {
+    let mut table = integer_table();
+    let solution = table.gomory().unwrap();
+    // the cuts are made in floating point, so the values are integer up to round-off
+    assert!((solution.objective - brute_force()).abs() < 1e-9);
+    assert!(solution
+        .variables
+        .iter()
+        .all(|x| (*x - x.round()).abs() < 1e-9));
+}
+
+#[test]
+fn gomory_stops_at_the_cut_limit() {
+    // the continuous optimum is fractional and no cut is allowed
+    let mut table = integer_table();
+    table.max_cuts = Some(0);
+    match table.gomory() {
+        Err(SimplexError::UnableToCalculateError) => {}
+        other => panic!("{:?}", other),
+    }
+}
@@ -0,0 +1,119 @@
+use ndarray::{arr1, arr2, Array1, Array2};
+use simplex_method::{Algorithm, Feasibility, PivotRule, Sense, SimplexError, Table};
+
+// Beale's example, cycles with the largest coeff rule
+fn beale() -> Table {
+    Table::new(
+        arr2(&[
+            [0.25, -8.0, -1.0, 9.0],
+            [0.5, -12.0, -0.5, 3.0],
+            [0.0, 0.0, 1.0, 0.0],
+        ]),
+        arr1(&[0.0, 0.0, 1.0]),
+        arr1(&[-0.75, 20.0, -0.5, 6.0]),
+        true,
+    )
+    .unwrap()
+}
+
+#[test]
+fn beale_cycles_with_dantzig_rule() {
+    let mut table = beale();
+    table.pivot_rule = PivotRule::Dantzig;
+    match table.optimise() {
+        Err(SimplexError::CyclingError(_, cycle)) => assert!(!cycle.is_empty()),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn beale_is_solved_with_bland_rule() {
+    let solution = beale().optimise().unwrap();
+    assert!((solution.objective + 1.25).abs() < 1e-9);
+}
+
+#[test]
+fn iteration_limit_is_reported() {
+    for algorithm in [Algorithm::Tableau, Algorithm::Revised] {
+        let mut table = beale();
+        table.algorithm = algorithm;
+        table.max_iterations = Some(2);
+        assert!(matches!(
+            table.optimise(),
+            Err(SimplexError::IterationLimitError(2))
+        ));
+    }
+}
+
+// x_i >= 1 for every i and sum x <= n + 1, max sum x, both phases pivot
+fn two_phases(n: usize) -> Table {
+    let mut constr_coeff = Array2::<f64>::zeros((n + 1, n));
+    for i in 0..n {
+        constr_coeff[[i, i]] = 1.0;
+        constr_coeff[[n, i]] = 1.0;
+    }
+    let mut constr_val = Array1::from_elem(n + 1, 1.0);
+    constr_val[n] = n as f64 + 1.0;
+    let mut table = Table::new(constr_coeff, constr_val, Array1::ones(n), false).unwrap();
+    let mut senses = vec![Sense::GreaterEqual; n];
+    senses.push(Sense::LessEqual);
+    table.set_senses(&senses).unwrap();
+    table
+}
+
+#[test]
+fn iteration_limit_counts_both_phases() {
+    for algorithm in [Algorithm::Tableau, Algorithm::Revised] {
+        let mut table = two_phases(3);
+        table.feasibility = Feasibility::TwoPhase;
+        table.algorithm = algorithm;
+        let iterations = table.optimise().unwrap().iterations;
+        let mut table = two_phases(3);
+        table.feasibility = Feasibility::TwoPhase;
+        table.algorithm = algorithm;
+        table.max_iterations = Some(iterations - 1);
+        assert!(matches!(
+            table.optimise(),
+            Err(SimplexError::IterationLimitError(i)) if i == iterations - 1
+        ));
+        let mut table = two_phases(3);
+        table.feasibility = Feasibility::TwoPhase;
+        table.algorithm = algorithm;
+        table.max_iterations = Some(iterations);
+        assert!(table.optimise().is_ok());
+    }
+}
+
+// Klee-Minty cube of dimension three: max 4x + 2y + z, x <= 5, 4x + y <= 25,
+// 8x + 4y + z <= 125, the largest coeff rule visits every vertex
+fn klee_minty() -> Table {
+    Table::new(
+        arr2(&[[1.0, 0.0, 0.0], [4.0, 1.0, 0.0], [8.0, 4.0, 1.0]]),
+        arr1(&[5.0, 25.0, 125.0]),
+        arr1(&[4.0, 2.0, 1.0]),
+        false,
+    )
+    .unwrap()
+}
+
+#[test]
+fn iterations_depend_on_pivot_rule() {
+    for (rule, iterations) in [
+        (PivotRule::Dantzig, Some(7)),
+        (PivotRule::GreatestImprovement, Some(1)),
+        (PivotRule::Bland, None),
+        (PivotRule::SteepestEdge, None),
+    ] {
+        for algorithm in [Algorithm::Tableau, Algorithm::Revised] {
+            let mut table = klee_minty();
+            table.pivot_rule = rule;
+            table.algorithm = algorithm;
+            let solution = table.optimise().unwrap();
+            assert_eq!(solution.objective, 125.0);
+            assert_eq!(solution.iterations, table.iterations);
+            if let Some(iterations) = iterations {
+                assert_eq!(solution.iterations, iterations, "{:?}", rule);
+            }
+        }
+    }
+}
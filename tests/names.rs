@@ -0,0 +1,53 @@
+use ndarray::{arr1, arr2};
+use simplex_method::{SimplexError, Table};
+
+// max x + 2y, x + y <= 4, x - y <= 1
+fn table() -> Table {
+    Table::new(
+        arr2(&[[1.0, 1.0], [1.0, -1.0]]),
+        arr1(&[4.0, 1.0]),
+        arr1(&[1.0, 2.0]),
+        false,
+    )
+    .unwrap()
+}
+
+#[test]
+fn names_are_carried_to_the_solution() {
+    let mut table = table();
+    table.set_names(&["x", "y"], &["budget", "gap"]).unwrap();
+    let solution = table.optimise().unwrap();
+    assert_eq!(solution.value("x"), Some(0.0));
+    assert_eq!(solution.value("y"), Some(4.0));
+    assert_eq!(solution.value("gap"), Some(5.0));
+    let sensitivity = table.sensitivity().unwrap();
+    assert_eq!(sensitivity.shadow_prices, vec![2.0, 0.0]);
+}
+
+#[test]
+fn reserved_names_are_rejected() {
+    let mut table = table();
+    assert!(matches!(
+        table.set_names(&["x", "y"], &["S", "gap"]),
+        Err(SimplexError::InvalidDataError)
+    ));
+    assert!(matches!(
+        table.set_names(&["F", "y"], &["budget", "gap"]),
+        Err(SimplexError::InvalidDataError)
+    ));
+    assert!(matches!(
+        table.set_names(&["x", "x"], &["budget", "gap"]),
+        Err(SimplexError::InvalidDataError)
+    ));
+}
+
+#[test]
+fn names_are_fixed_after_optimising() {
+    let mut table = table();
+    table.optimise().unwrap();
+    assert!(matches!(
+        table.set_names(&["x", "y"], &["budget", "gap"]),
+        Err(SimplexError::InvalidDataError)
+    ));
+    assert_eq!(table.solution().variables, vec![0.0, 4.0]);
+}
@@ -0,0 +1,54 @@
+use ndarray::{arr1, arr2};
+use simplex_method::{Reduction, Sense, SimplexError, Table};
+
+#[test]
+fn crossed_bounds_make_presolve_infeasible() {
+    // max -3x + y + z, 2y >= -1, 2 <= x <= 1
+    let mut table: Table = Table::new(
+        arr2(&[[0.0, 2.0, 0.0]]),
+        arr1(&[-1.0]),
+        arr1(&[-3.0, 1.0, 1.0]),
+        false,
+    )
+    .unwrap();
+    table.set_senses(&[Sense::GreaterEqual]).unwrap();
+    table.lower_bounds = vec![Some(2.0), Some(0.0), Some(0.0)];
+    table.upper_bounds = vec![Some(1.0), None, None];
+    assert!(matches!(
+        table.presolve(),
+        Err(SimplexError::NoSolutionsError(_))
+    ));
+}
+
+#[test]
+fn postsolve_matches_the_original_task() {
+    // max 2x + 3y + z + w, x + y + z <= 10, 2x + 2y + 2z <= 30, y <= 4, 0 <= 5,
+    // x + z <= 7, with w fixed at 2
+    let mut table: Table = Table::new(
+        arr2(&[
+            [1.0, 1.0, 1.0, 0.0],
+            [2.0, 2.0, 2.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 0.0],
+            [1.0, 0.0, 1.0, 0.0],
+        ]),
+        arr1(&[10.0, 30.0, 4.0, 5.0, 7.0]),
+        arr1(&[2.0, 3.0, 1.0, 1.0]),
+        false,
+    )
+    .unwrap();
+    table.lower_bounds[3] = Some(2.0);
+    table.upper_bounds[3] = Some(2.0);
+    let (mut presolved, postsolve) = table.presolve().unwrap();
+    assert!(presolved.constraints.len() < table.constraints.len());
+    assert!(postsolve.reductions.iter().any(|reduction| matches!(
+        reduction,
+        Reduction::FixedColumn(_, value) if *value == 2.0
+    )));
+    let solution = postsolve.solution(&presolved.optimise().unwrap());
+    let expected = table.optimise().unwrap();
+    assert_eq!(solution.objective, 26.0);
+    assert_eq!(solution.objective, expected.objective);
+    assert_eq!(solution.variables, expected.variables);
+    assert_eq!(solution.slacks, expected.slacks);
+}
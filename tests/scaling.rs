@@ -0,0 +1,48 @@
+use ndarray::{arr1, arr2};
+use simplex_method::{Scaling, Sense, Table};
+
+// max 3000x + 0.005y, 1000x <= 4000, 0.002y <= 0.012, 3x + 2y >= 1, 3x + 2y <= 18,
+// with coeffs that differ by orders of magnitude
+fn table(scaling: Scaling) -> Table {
+    let mut table = Table::new(
+        arr2(&[[1000.0, 0.0], [0.0, 0.002], [3.0, 2.0], [3.0, 2.0]]),
+        arr1(&[4000.0, 0.012, 1.0, 18.0]),
+        arr1(&[3000.0, 0.005]),
+        false,
+    )
+    .unwrap();
+    table
+        .set_senses(&[
+            Sense::LessEqual,
+            Sense::LessEqual,
+            Sense::GreaterEqual,
+            Sense::LessEqual,
+        ])
+        .unwrap();
+    table.scaling = scaling;
+    table
+}
+
+fn close(a: &[f64], b: &[f64]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b.iter())
+            .all(|(a, b)| (a - b).abs() <= 1e-9 * (1.0 + b.abs()))
+}
+
+#[test]
+fn scaled_solve_matches_unscaled_one() {
+    let mut unscaled = table(Scaling::None);
+    let expected = unscaled.optimise().unwrap();
+    let prices = unscaled.sensitivity().unwrap();
+    for scaling in [Scaling::GeometricMean, Scaling::Equilibration] {
+        let mut scaled = table(scaling);
+        let solution = scaled.optimise().unwrap();
+        assert!(close(&[solution.objective], &[expected.objective]));
+        assert!(close(&solution.variables, &expected.variables));
+        assert!(close(&solution.slacks, &expected.slacks));
+        let sensitivity = scaled.sensitivity().unwrap();
+        assert!(close(&sensitivity.shadow_prices, &prices.shadow_prices));
+        assert!(close(&sensitivity.reduced_costs, &prices.reduced_costs));
+    }
+}
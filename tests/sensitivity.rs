@@ -0,0 +1,32 @@
+use ndarray::{arr1, arr2};
+use simplex_method::Table;
+
+#[test]
+fn ranging_of_the_textbook_model() {
+    // max 3x + 5y, x <= 4, 2y <= 12, 3x + 2y <= 18, optimal at x = 2, y = 6
+    let mut table: Table = Table::new(
+        arr2(&[[1.0, 0.0], [0.0, 2.0], [3.0, 2.0]]),
+        arr1(&[4.0, 12.0, 18.0]),
+        arr1(&[3.0, 5.0]),
+        false,
+    )
+    .unwrap();
+    table.optimise().unwrap();
+    let sensitivity = table.sensitivity().unwrap();
+    assert_eq!(sensitivity.shadow_prices, vec![0.0, 1.5, 1.0]);
+    assert_eq!(sensitivity.reduced_costs, vec![0.0, 0.0]);
+    // 0 <= c1 <= 7.5 and c2 >= 2
+    assert_eq!(
+        sensitivity.objective_ranges,
+        vec![(Some(3.0), Some(4.5)), (Some(3.0), None)]
+    );
+    // b1 >= 2, 6 <= b2 <= 18 and 12 <= b3 <= 24
+    assert_eq!(
+        sensitivity.rhs_ranges,
+        vec![
+            (Some(2.0), None),
+            (Some(6.0), Some(6.0)),
+            (Some(6.0), Some(6.0))
+        ]
+    );
+}
@@ -0,0 +1,87 @@
+use ndarray::{arr1, arr2, Array1, Array2};
+use simplex_method::{Algorithm, Sense, SparseMatrix, Table};
+
+// pseudo-random sparse model: min c x, A x >= b with every fifth row A x <= 1000
+fn model(size: usize) -> (Array2<f64>, Array1<f64>, Array1<f64>, Vec<Sense>) {
+    let mut seed: u64 = 7;
+    let mut next = move || {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (seed >> 33) % 100
+    };
+    let mut a = Array2::zeros((size, size));
+    for i in 0..size {
+        a[[i, i]] = 1.0 + next() as f64 / 10.0;
+        for _ in 0..3 {
+            let j = next() as usize % size;
+            a[[i, j]] += next() as f64 / 10.0;
+        }
+    }
+    let b = Array1::from_shape_fn(size, |i| {
+        if i % 5 == 0 {
+            1000.0
+        } else {
+            10.0 + next() as f64
+        }
+    });
+    let c = Array1::from_shape_fn(size, |_| 1.0 + next() as f64 / 10.0);
+    let senses = (0..size)
+        .map(|i| {
+            if i % 5 == 0 {
+                Sense::LessEqual
+            } else {
+                Sense::GreaterEqual
+            }
+        })
+        .collect();
+    (a, b, c, senses)
+}
+
+fn sparse(a: &Array2<f64>) -> SparseMatrix {
+    let triplets: Vec<(usize, usize, f64)> = a
+        .indexed_iter()
+        .filter(|(_, &value)| value != 0.0)
+        .map(|((i, j), &value)| (i, j, value))
+        .collect();
+    SparseMatrix::from_triplets(a.nrows(), a.ncols(), &triplets).unwrap()
+}
+
+#[test]
+fn sparse_table_reads_like_dense_one() {
+    let a = arr2(&[[1.0, 0.0, 2.0], [0.0, 3.0, 0.0]]);
+    let b = arr1(&[4.0, 5.0]);
+    let c = arr1(&[1.0, 1.0, 1.0]);
+    let dense = Table::new(a.clone(), b.clone(), c.clone(), false).unwrap();
+    let sparse = Table::from_sparse(sparse(&a), b, c, false).unwrap();
+    assert_eq!(sparse.table(), dense.table());
+    assert_eq!(sparse.algorithm, Algorithm::Revised);
+}
+
+#[test]
+fn sparse_model_agrees_with_dense_one() {
+    // enough pivots for the basis to be factorised again on the way
+    let (a, b, c, senses) = model(120);
+    let mut dense = Table::new(a.clone(), b.clone(), c.clone(), true).unwrap();
+    dense.set_senses(&senses).unwrap();
+    let mut sparse = Table::from_sparse(sparse(&a), b, c, true).unwrap();
+    sparse.set_senses(&senses).unwrap();
+    let expected = dense.optimise().unwrap();
+    let solution = sparse.optimise().unwrap();
+    assert!(sparse.iterations > 64);
+    assert!((solution.objective - expected.objective).abs() < 1e-6);
+}
+
+#[test]
+fn sparse_table_prints_only_non_zeros() {
+    let a = arr2(&[[1.0, 0.0, 2.0], [0.0, 3.0, 0.0]]);
+    let table =
+        Table::from_sparse(sparse(&a), arr1(&[4.0, 5.0]), arr1(&[1.0, 1.0, 1.0]), false).unwrap();
+    let printed = table.to_string();
+    let mut lines = printed.lines();
+    assert_eq!(
+        lines.next(),
+        Some("4\t| 1: 1.0000000\t| 3: 2.0000000\t| S: 4.0000000")
+    );
+    assert_eq!(lines.next(), Some("5\t| 2: 3.0000000\t| S: 5.0000000"));
+}
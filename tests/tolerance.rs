@@ -0,0 +1,50 @@
+use ndarray::{arr1, arr2};
+use simplex_method::{Algorithm, SimplexError, Table, Tolerance};
+
+// max x + y, x + y <= 1, x <= -1e-17, the second right-hand side is round-off of zero
+fn rounded(tolerance: Tolerance) -> Table {
+    let mut table = Table::new(
+        arr2(&[[1.0, 1.0], [1.0, 0.0]]),
+        arr1(&[1.0, -1e-17]),
+        arr1(&[1.0, 1.0]),
+        false,
+    )
+    .unwrap();
+    table.tolerance = tolerance;
+    table
+}
+
+#[test]
+fn round_off_below_zero_counts_as_feasible() {
+    let solution = rounded(Tolerance::default()).optimise().unwrap();
+    assert_eq!(solution.objective, 1.0);
+    // without a tolerance the same row can't hold for any x >= 0
+    let exact = Tolerance {
+        pivot: 0.0,
+        optimality: 0.0,
+        feasibility: 0.0,
+    };
+    assert!(matches!(
+        rounded(exact).optimise(),
+        Err(SimplexError::NoSolutionsError(_))
+    ));
+}
+
+#[test]
+fn negative_zero_coeffs_are_never_pivoted_on() {
+    // max -0x + y, -0x + y <= 2, x + y <= 3, x has nothing to gain
+    for algorithm in [Algorithm::Tableau, Algorithm::Revised] {
+        let mut table: Table = Table::new(
+            arr2(&[[-0.0, 1.0], [1.0, 1.0]]),
+            arr1(&[2.0, 3.0]),
+            arr1(&[-0.0, 1.0]),
+            false,
+        )
+        .unwrap();
+        table.algorithm = algorithm;
+        let solution = table.optimise().unwrap();
+        assert_eq!(solution.objective, 2.0);
+        assert_eq!(solution.variables, vec![0.0, 2.0]);
+        assert_eq!(solution.iterations, 1);
+    }
+}
@@ -0,0 +1,38 @@
+use ndarray::{arr1, arr2, concatenate, Axis};
+use simplex_method::{Sense, Table};
+
+// max 3x + 2y, x + y <= 4, x + 3y <= 6, x <= 3, optimal at x = 3, y = 1
+fn table(extra: Option<([f64; 2], Sense, f64)>) -> Table {
+    let mut constr_coeff = arr2(&[[1.0, 1.0], [1.0, 3.0], [1.0, 0.0]]);
+    let mut constr_val = arr1(&[4.0, 6.0, 3.0]);
+    let mut senses = vec![Sense::LessEqual; 3];
+    if let Some((coeffs, sense, value)) = extra {
+        constr_coeff = concatenate![Axis(0), constr_coeff, arr2(&[coeffs])];
+        constr_val = concatenate![Axis(0), constr_val, arr1(&[value])];
+        senses.push(sense);
+    }
+    let mut table = Table::new(constr_coeff, constr_val, arr1(&[3.0, 2.0]), false).unwrap();
+    table.set_senses(&senses).unwrap();
+    table
+}
+
+#[test]
+fn added_constraint_matches_cold_solve() {
+    for (coeffs, sense, value) in [
+        ([2.0, 1.0], Sense::LessEqual, 6.0),
+        ([0.0, 1.0], Sense::GreaterEqual, 2.0),
+        ([1.0, -1.0], Sense::Equal, 1.0),
+    ] {
+        let mut warm = table(None);
+        warm.optimise().unwrap();
+        let iterations = warm.iterations;
+        let solution = warm.add_constraint(&coeffs, sense, value).unwrap();
+        let expected = table(Some((coeffs, sense, value))).optimise().unwrap();
+        assert!((solution.objective - expected.objective).abs() < 1e-9);
+        for (x, y) in solution.variables.iter().zip(expected.variables.iter()) {
+            assert!((x - y).abs() < 1e-9);
+        }
+        // the old optimum violates every added row, so the dual simplex had to pivot
+        assert!(warm.iterations > iterations);
+    }
+}